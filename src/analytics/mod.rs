@@ -0,0 +1 @@
+pub mod rating_table;
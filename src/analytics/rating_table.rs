@@ -0,0 +1,314 @@
+use crate::lichess::perf_type::PerfType;
+use crate::lichess::rating_history::RatingHistory;
+
+use chrono::{Duration, NaiveDate};
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+
+use std::io::{self, Write};
+
+/// The interval between consecutive rows of a [`RatingTable`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Resolution {
+    Daily,
+    Weekly,
+}
+
+impl Resolution {
+    fn step(self) -> Duration {
+        match self {
+            Resolution::Daily => Duration::days(1),
+            Resolution::Weekly => Duration::weeks(1),
+        }
+    }
+}
+
+/// Rating histories resampled to a regular series of dates, with one column
+/// per series. Lichess only records a rating on days on which it changed, so
+/// each rating is carried forward until the next change. Dates before the
+/// first rating of a series have no value in its column.
+///
+/// Column names are distinct, and never "date", so that each names a single
+/// field of a written row. A column which would take a name already in use is
+/// named with a "_2" suffix instead, or "_3" and so on.
+#[derive(Clone, Default, Debug)]
+pub struct RatingTable {
+    pub dates: Vec<NaiveDate>,
+    pub columns: Vec<RatingColumn>,
+    /// The number of leading columns holding ratings rather than values
+    /// derived from them.
+    ratings: usize,
+}
+
+/// A named column of a [`RatingTable`], with one value per date.
+#[derive(Clone, Debug)]
+pub struct RatingColumn {
+    pub name: String,
+    pub values: Vec<Option<f64>>,
+}
+
+impl RatingTable {
+    /// Resample the rating histories of several users in a single perf to a
+    /// common series of dates, with one column per user named after them. The
+    /// series spans from the earliest rating of any user to the latest.
+    /// Returns an empty table if none of the users have a rating in the perf.
+    pub fn compare(
+        histories: &[(&str, &RatingHistory)],
+        perf: PerfType,
+        resolution: Resolution,
+    ) -> Self {
+        let records = histories.iter().flat_map(|(_, history)| history.get(perf));
+
+        match (
+            records.clone().map(|record| record.date).min(),
+            records.map(|record| record.date).max(),
+        ) {
+            (Some(start), Some(end)) => {
+                Self::compare_between(histories, perf, resolution, start, end)
+            }
+            _ => RatingTable::default(),
+        }
+    }
+
+    /// Resample the rating histories of several users in a single perf to the
+    /// series of dates from `start` to `end` inclusive, with one column per
+    /// user named after them.
+    pub fn compare_between(
+        histories: &[(&str, &RatingHistory)],
+        perf: PerfType,
+        resolution: Resolution,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Self {
+        let dates = series(start, end, resolution);
+        let mut columns: Vec<RatingColumn> = Vec::with_capacity(histories.len());
+        for (name, history) in histories {
+            let values = dates
+                .iter()
+                .map(|date| history.rating_at(perf, *date).map(f64::from))
+                .collect();
+            columns.push(RatingColumn {
+                name: unique_name(&columns, name),
+                values,
+            });
+        }
+
+        RatingTable {
+            dates,
+            ratings: columns.len(),
+            columns,
+        }
+    }
+
+    /// Resample the rating history of a single user in every perf in which
+    /// they have a rating, with one column per perf named after its key. The
+    /// series spans from the user's earliest rating in any perf to their
+    /// latest.
+    pub fn perfs(history: &RatingHistory, resolution: Resolution) -> Self {
+        let (start, end) = match (
+            history
+                .iter()
+                .filter_map(|(_, records)| records.first())
+                .map(|record| record.date)
+                .min(),
+            history
+                .iter()
+                .filter_map(|(_, records)| records.last())
+                .map(|record| record.date)
+                .max(),
+        ) {
+            (Some(start), Some(end)) => (start, end),
+            _ => return RatingTable::default(),
+        };
+
+        let dates = series(start, end, resolution);
+        let columns: Vec<RatingColumn> = history
+            .iter()
+            .filter(|(_, records)| !records.is_empty())
+            .map(|(perf, _)| RatingColumn {
+                name: perf.key().to_string(),
+                values: dates
+                    .iter()
+                    .map(|date| history.rating_at(perf, *date).map(f64::from))
+                    .collect(),
+            })
+            .collect();
+
+        RatingTable {
+            dates,
+            ratings: columns.len(),
+            columns,
+        }
+    }
+
+    /// Add a column with the rolling average of every rating column over a
+    /// window of the given number of rows, named after the column with an
+    /// "_avg" suffix and the window size. Rows without a value are ignored,
+    /// and the average is absent if no row in the window has a value.
+    pub fn add_rolling_average(&mut self, window: usize) {
+        let averages: Vec<RatingColumn> = self.columns[..self.ratings]
+            .iter()
+            .map(|column| RatingColumn {
+                name: format!("{}_avg{}", column.name, window),
+                values: rolling(&column.values, window, |values| {
+                    Some(values.iter().sum::<f64>() / values.len() as f64)
+                }),
+            })
+            .collect();
+        self.extend(averages);
+    }
+
+    /// Add a column with the rolling volatility of every rating column over
+    /// a window of the given number of rows, named after the column with a
+    /// "_vol" suffix and the window size. Volatility is the standard deviation
+    /// of the changes in rating between consecutive rows, and is absent if the
+    /// window contains fewer than two values.
+    pub fn add_volatility(&mut self, window: usize) {
+        let volatilities: Vec<RatingColumn> = self.columns[..self.ratings]
+            .iter()
+            .map(|column| RatingColumn {
+                name: format!("{}_vol{}", column.name, window),
+                values: rolling(&column.values, window, |values| {
+                    let changes: Vec<f64> =
+                        values.windows(2).map(|pair| pair[1] - pair[0]).collect();
+                    if changes.is_empty() {
+                        return None;
+                    }
+                    let mean = changes.iter().sum::<f64>() / changes.len() as f64;
+                    let variance = changes
+                        .iter()
+                        .map(|change| (change - mean).powi(2))
+                        .sum::<f64>()
+                        / changes.len() as f64;
+                    Some(variance.sqrt())
+                }),
+            })
+            .collect();
+        self.extend(volatilities);
+    }
+
+    /// Append derived columns, renaming any whose name is already in use.
+    fn extend(&mut self, columns: Vec<RatingColumn>) {
+        for column in columns {
+            let name = unique_name(&self.columns, &column.name);
+            self.columns.push(RatingColumn { name, ..column });
+        }
+    }
+
+    /// Write the table as CSV, with a header row, a date column, and one
+    /// column per column of the table. Missing values are left empty.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let mut header = vec!["date".to_string()];
+        header.extend(self.columns.iter().map(|column| csv_field(&column.name)));
+        writeln!(writer, "{}", header.join(","))?;
+
+        for (i, date) in self.dates.iter().enumerate() {
+            let mut row = vec![date.to_string()];
+            row.extend(self.columns.iter().map(|column| match column.values[i] {
+                Some(value) => format_value(value),
+                None => String::new(),
+            }));
+            writeln!(writer, "{}", row.join(","))?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the table as a JSON array with one object per row, keyed by
+    /// "date" and the names of the columns in order. Missing values are null.
+    pub fn write_json<W: Write>(&self, writer: W) -> io::Result<()> {
+        let rows: Vec<JsonRow> = (0..self.dates.len())
+            .map(|row| JsonRow { table: self, row })
+            .collect();
+        serde_json::to_writer(writer, &rows)?;
+        Ok(())
+    }
+}
+
+/// A row of a [`RatingTable`], serialized as a JSON object whose keys are in
+/// the order of the columns.
+struct JsonRow<'a> {
+    table: &'a RatingTable,
+    row: usize,
+}
+
+impl Serialize for JsonRow<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.table.columns.len() + 1))?;
+        map.serialize_entry("date", &self.table.dates[self.row])?;
+        for column in &self.table.columns {
+            map.serialize_entry(&column.name, &column.values[self.row])?;
+        }
+        map.end()
+    }
+}
+
+/// The given name if no column has it and it is not "date", or otherwise the
+/// name with the first of the suffixes "_2", "_3" and so on which makes it so.
+fn unique_name(columns: &[RatingColumn], name: &str) -> String {
+    let taken = |candidate: &str| {
+        candidate == "date" || columns.iter().any(|column| column.name == candidate)
+    };
+    if !taken(name) {
+        return name.to_string();
+    }
+    (2..)
+        .map(|n| format!("{}_{}", name, n))
+        .find(|candidate| !taken(candidate))
+        .unwrap()
+}
+
+/// The dates from `start` at the given resolution, up to the first date on or
+/// after `end`, so that the last row reflects every rating up to `end`.
+fn series(start: NaiveDate, end: NaiveDate, resolution: Resolution) -> Vec<NaiveDate> {
+    let mut dates = vec![start];
+    let mut date = start;
+    while date < end {
+        date += resolution.step();
+        dates.push(date);
+    }
+    dates
+}
+
+/// Apply a function to the values present in each window of the given size
+/// ending at each row.
+fn rolling<F>(values: &[Option<f64>], window: usize, f: F) -> Vec<Option<f64>>
+where
+    F: Fn(&[f64]) -> Option<f64>,
+{
+    (0..values.len())
+        .map(|i| {
+            let start = (i + 1).saturating_sub(window.max(1));
+            let present: Vec<f64> = values[start..=i].iter().flatten().copied().collect();
+            if present.is_empty() {
+                None
+            } else {
+                f(&present)
+            }
+        })
+        .collect()
+}
+
+/// Ratings are integers, so are written without a fractional part, while
+/// derived values are rounded to two decimal places.
+fn format_value(value: f64) -> String {
+    let rounded = (value * 100.0).round() / 100.0;
+    if rounded.fract() == 0.0 {
+        format!("{}", rounded as i64)
+    } else {
+        format!("{:.2}", rounded)
+    }
+}
+
+/// Quote a CSV field if it contains characters which would otherwise break
+/// the row.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -0,0 +1,141 @@
+use crate::core::client::Client;
+use crate::core::ndjson::ndjson::NDJsonStream;
+use crate::core::pgn::PgnStream;
+use crate::lichess::broadcast::{Broadcast, BroadcastPushResult, BroadcastRoundWithTour};
+
+use chrono::{DateTime, Utc};
+use hyper::Body;
+
+use std::error::Error;
+
+/// The settings of a broadcast tournament, used to create a new tournament or
+/// to update an existing one.
+#[derive(Default, Clone, Debug)]
+pub struct BroadcastTourSettings {
+    pub name: String,
+    // A short description, shown in lists of broadcasts.
+    pub description: String,
+    // A full description in Markdown, shown on the tournament page.
+    pub markdown: Option<String>,
+    pub auto_leaderboard: bool,
+}
+
+impl BroadcastTourSettings {
+    fn fields(&self) -> Vec<(&str, &str)> {
+        let mut fields = vec![
+            ("name", self.name.as_str()),
+            ("description", self.description.as_str()),
+            ("autoLeaderboard", if self.auto_leaderboard { "true" } else { "false" }),
+        ];
+        if let Some(markdown) = &self.markdown {
+            fields.push(("markdown", markdown));
+        }
+        fields
+    }
+}
+
+/// The settings of a broadcast round, used to create a new round or to update
+/// an existing one. Rounds without a sync URL are updated by pushing PGN.
+#[derive(Default, Clone, Debug)]
+pub struct BroadcastRoundSettings {
+    pub name: String,
+    pub sync_url: Option<String>,
+    pub starts_at: Option<DateTime<Utc>>,
+}
+
+impl BroadcastRoundSettings {
+    fn fields(&self) -> Vec<(&str, String)> {
+        let mut fields = vec![("name", self.name.clone())];
+        if let Some(sync_url) = &self.sync_url {
+            fields.push(("syncUrl", sync_url.clone()));
+        }
+        if let Some(starts_at) = self.starts_at {
+            fields.push(("startsAt", starts_at.timestamp_millis().to_string()));
+        }
+        fields
+    }
+}
+
+impl Client {
+    /// Stream the official broadcasts, ongoing broadcasts first, then upcoming
+    /// and finished broadcasts. At most `nb` broadcasts are streamed.
+    pub async fn get_official_broadcasts(&self, nb: u32) -> Result<NDJsonStream<Broadcast>, Box<dyn Error>> {
+        self.get_ndjson(&format!("{}/api/broadcast?nb={}", self.lichess_host(), nb)).await
+    }
+
+    /// Create a new broadcast tournament, without any rounds. Requires an
+    /// authenticated client.
+    pub async fn create_broadcast_tour(&self, settings: &BroadcastTourSettings) -> Result<Broadcast, Box<dyn Error>> {
+        self.require_token()?;
+        let endpoint = format!("{}/broadcast/new", self.lichess_host());
+        self.post_form_json(&endpoint, &settings.fields()).await
+    }
+
+    /// Update the settings of a broadcast tournament. Requires an
+    /// authenticated client.
+    pub async fn update_broadcast_tour(
+        &self,
+        tour_id: &str,
+        settings: &BroadcastTourSettings,
+    ) -> Result<(), Box<dyn Error>> {
+        self.require_token()?;
+        let endpoint = format!("{}/broadcast/{}/edit", self.lichess_host(), tour_id);
+        self.post_form_json::<_, serde_json::Value>(&endpoint, &settings.fields()).await?;
+        Ok(())
+    }
+
+    /// Create a new round in a broadcast tournament. Requires an
+    /// authenticated client.
+    pub async fn create_broadcast_round(
+        &self,
+        tour_id: &str,
+        settings: &BroadcastRoundSettings,
+    ) -> Result<BroadcastRoundWithTour, Box<dyn Error>> {
+        self.require_token()?;
+        let endpoint = format!("{}/broadcast/{}/new", self.lichess_host(), tour_id);
+        self.post_form_json(&endpoint, &settings.fields()).await
+    }
+
+    /// Update the settings of a broadcast round. Requires an authenticated
+    /// client.
+    pub async fn update_broadcast_round(
+        &self,
+        round_id: &str,
+        settings: &BroadcastRoundSettings,
+    ) -> Result<BroadcastRoundWithTour, Box<dyn Error>> {
+        self.require_token()?;
+        let endpoint = format!("{}/broadcast/round/{}/edit", self.lichess_host(), round_id);
+        self.post_form_json(&endpoint, &settings.fields()).await
+    }
+
+    /// Push the PGN of one or more games to a broadcast round. Games are
+    /// matched to the games already in the round by their tags, so the full
+    /// PGN of every game should be pushed each time. Requires an authenticated
+    /// client.
+    pub async fn push_broadcast_pgn(&self, round_id: &str, pgn: &str) -> Result<BroadcastPushResult, Box<dyn Error>> {
+        self.require_token()?;
+        let endpoint = format!("{}/api/broadcast/round/{}/push", self.lichess_host(), round_id);
+        let resp = self
+            .post(&endpoint, Body::from(pgn.to_string()), Some("text/plain"))
+            .await?;
+        let bytes = hyper::body::to_bytes(resp).await?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    /// Stream the games of a broadcast round as PGN. Every game is streamed
+    /// once when the stream starts, and again each time it is updated.
+    pub async fn stream_broadcast_round(&self, round_id: &str) -> Result<PgnStream, Box<dyn Error>> {
+        let endpoint = format!("{}/api/stream/broadcast/round/{}.pgn", self.lichess_host(), round_id);
+        self.get_pgn(&endpoint).await
+    }
+
+    /// Export the games of a broadcast round as PGN.
+    pub async fn export_broadcast_round(&self, round_id: &str) -> Result<String, Box<dyn Error>> {
+        self.get_text(&format!("{}/api/broadcast/round/{}.pgn", self.lichess_host(), round_id)).await
+    }
+
+    /// Export the games of every round of a broadcast tournament as PGN.
+    pub async fn export_broadcast_tour(&self, tour_id: &str) -> Result<String, Box<dyn Error>> {
+        self.get_text(&format!("{}/api/broadcast/{}.pgn", self.lichess_host(), tour_id)).await
+    }
+}
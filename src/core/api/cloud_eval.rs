@@ -0,0 +1,111 @@
+use crate::core::client::{urlencode, Client, ClientError};
+use crate::lichess::cloud_eval::CloudEval;
+use crate::lichess::variant::Variant;
+
+use hyper::StatusCode;
+use tokio::time::Instant;
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::sync::Mutex;
+use std::time::Duration;
+
+impl Client {
+    /// Get the cloud evaluation of a position with the given number of
+    /// principal variations. Only positions which have been analysed by users
+    /// are in the cloud, so this returns `None` for most positions outside of
+    /// common openings.
+    pub async fn get_cloud_eval(
+        &self,
+        fen: &str,
+        multi_pv: u32,
+        variant: Option<Variant>,
+    ) -> Result<Option<CloudEval>, Box<dyn Error>> {
+        let mut endpoint = format!(
+            "{}/api/cloud-eval?fen={}&multiPv={}",
+            self.lichess_host(),
+            urlencode(fen),
+            multi_pv,
+        );
+        if let Some(variant) = variant {
+            endpoint.push_str(&format!("&variant={}", variant));
+        }
+
+        match self.get_json(&endpoint).await {
+            Ok(eval) => Ok(Some(eval)),
+            Err(e) => match e.downcast_ref::<ClientError>() {
+                Some(ClientError::UnexpectedStatus(StatusCode::NOT_FOUND)) => Ok(None),
+                _ => Err(e),
+            },
+        }
+    }
+}
+
+/// A cache of cloud evaluations, so that repeatedly looking up the same
+/// position does not count against the rate limit. Positions missing from the
+/// cloud are cached as well. Entries expire after a configurable time to live,
+/// since the cloud evaluation of a position deepens as it is analysed further.
+pub struct CloudEvalCache<'a> {
+    client: &'a Client,
+    ttl: Duration,
+    entries: Mutex<HashMap<CloudEvalKey, (Instant, Option<CloudEval>)>>,
+}
+
+#[derive(PartialEq, Eq, Hash)]
+struct CloudEvalKey {
+    fen: String,
+    multi_pv: u32,
+    variant: Option<Variant>,
+}
+
+impl<'a> CloudEvalCache<'a> {
+    pub fn new(client: &'a Client, ttl: Duration) -> Self {
+        CloudEvalCache {
+            client,
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Get the cloud evaluation of a position, from the cache if it holds an
+    /// unexpired entry for the position, otherwise from the API.
+    pub async fn get(
+        &self,
+        fen: &str,
+        multi_pv: u32,
+        variant: Option<Variant>,
+    ) -> Result<Option<CloudEval>, Box<dyn Error>> {
+        let key = CloudEvalKey {
+            fen: fen.trim().to_string(),
+            multi_pv,
+            variant,
+        };
+
+        if let Some((fetched_at, eval)) = self.entries.lock().unwrap().get(&key) {
+            if fetched_at.elapsed() < self.ttl {
+                return Ok(eval.clone());
+            }
+        }
+
+        let eval = self.client.get_cloud_eval(&key.fen, multi_pv, variant).await?;
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(key, (Instant::now(), eval.clone()));
+        Ok(eval)
+    }
+
+    /// Remove all expired entries from the cache.
+    pub fn evict_expired(&self) {
+        let ttl = self.ttl;
+        self.entries
+            .lock()
+            .unwrap()
+            .retain(|_, (fetched_at, _)| fetched_at.elapsed() < ttl);
+    }
+
+    /// Remove all entries from the cache.
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
@@ -0,0 +1,111 @@
+use crate::core::client::{urlencode, Client};
+use crate::core::ndjson::ndjson::NDJsonStream;
+use crate::lichess::explorer::ExplorerResult;
+use crate::lichess::game::Color;
+use crate::lichess::speed::Speed;
+use crate::lichess::variant::Variant;
+
+use std::error::Error;
+
+/// Options for querying the opening explorer. The position is given by a
+/// starting FEN, defaulting to the standard starting position, and a list of
+/// UCI moves played from it. Not every option is supported by every database:
+/// `speeds` and `ratings` only apply to the Lichess database, `modes` only to
+/// the player database, and `variant` to both but not the masters database.
+#[derive(Default, Clone, Debug)]
+pub struct ExplorerOptions {
+    pub variant: Option<Variant>,
+    pub fen: Option<String>,
+    pub play: Vec<String>,
+    // Formatted as YYYY-MM, or as YYYY for the masters database.
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub moves: Option<u32>,
+    pub top_games: Option<u32>,
+    pub recent_games: Option<u32>,
+    pub speeds: Vec<Speed>,
+    // The lower bounds of the rating buckets to include, such as 1600 or 2500.
+    pub ratings: Vec<u32>,
+    pub modes: Vec<String>,
+}
+
+impl ExplorerOptions {
+    /// Encode the options which are set as a query string, without the
+    /// leading question mark.
+    fn query(&self) -> String {
+        let mut params = Vec::new();
+
+        if let Some(variant) = &self.variant {
+            params.push(format!("variant={}", variant));
+        }
+        if let Some(fen) = &self.fen {
+            params.push(format!("fen={}", urlencode(fen)));
+        }
+        if !self.play.is_empty() {
+            params.push(format!("play={}", self.play.join(",")));
+        }
+        if let Some(since) = &self.since {
+            params.push(format!("since={}", since));
+        }
+        if let Some(until) = &self.until {
+            params.push(format!("until={}", until));
+        }
+        if let Some(moves) = self.moves {
+            params.push(format!("moves={}", moves));
+        }
+        if let Some(top_games) = self.top_games {
+            params.push(format!("topGames={}", top_games));
+        }
+        if let Some(recent_games) = self.recent_games {
+            params.push(format!("recentGames={}", recent_games));
+        }
+        if !self.speeds.is_empty() {
+            let speeds: Vec<&str> = self.speeds.iter().map(|speed| speed.key()).collect();
+            params.push(format!("speeds={}", speeds.join(",")));
+        }
+        if !self.ratings.is_empty() {
+            let ratings: Vec<String> = self.ratings.iter().map(u32::to_string).collect();
+            params.push(format!("ratings={}", ratings.join(",")));
+        }
+        if !self.modes.is_empty() {
+            params.push(format!("modes={}", self.modes.join(",")));
+        }
+
+        params.join("&")
+    }
+}
+
+impl Client {
+    /// Look up a position in the database of over-the-board games between
+    /// masters.
+    pub async fn explore_masters(&self, options: &ExplorerOptions) -> Result<ExplorerResult, Box<dyn Error>> {
+        let endpoint = format!("{}/masters?{}", self.explorer_host(), options.query());
+        self.get_json(&endpoint).await
+    }
+
+    /// Look up a position in the database of games played on Lichess.
+    pub async fn explore_lichess(&self, options: &ExplorerOptions) -> Result<ExplorerResult, Box<dyn Error>> {
+        let endpoint = format!("{}/lichess?{}", self.explorer_host(), options.query());
+        self.get_json(&endpoint).await
+    }
+
+    /// Look up a position in the games of a single player, playing the given
+    /// color. Players whose games have not yet been indexed are indexed on
+    /// demand, so the stream yields progressively more complete results until
+    /// indexing is finished.
+    pub async fn explore_player(
+        &self,
+        player: &str,
+        color: Color,
+        options: &ExplorerOptions,
+    ) -> Result<NDJsonStream<ExplorerResult>, Box<dyn Error>> {
+        let endpoint = format!(
+            "{}/player?player={}&color={}&{}",
+            self.explorer_host(),
+            urlencode(player),
+            color,
+            options.query(),
+        );
+        self.get_ndjson(&endpoint).await
+    }
+}
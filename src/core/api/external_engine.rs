@@ -0,0 +1,87 @@
+use crate::core::client::Client;
+use crate::lichess::external_engine::{EngineWork, ExternalEngine, ExternalEngineRegistration};
+
+use hyper::{Body, Method};
+use serde::Serialize;
+
+use std::error::Error;
+
+impl Client {
+    /// List the external engines registered by the authenticated user.
+    /// Requires an authenticated client.
+    pub async fn list_external_engines(&self) -> Result<Vec<ExternalEngine>, Box<dyn Error>> {
+        self.require_token()?;
+        self.get_json(&format!("{}/api/external-engine", self.lichess_host())).await
+    }
+
+    /// Register a new external engine for the authenticated user. Requires an
+    /// authenticated client.
+    pub async fn create_external_engine(
+        &self,
+        registration: &ExternalEngineRegistration,
+    ) -> Result<ExternalEngine, Box<dyn Error>> {
+        self.require_token()?;
+        let endpoint = format!("{}/api/external-engine", self.lichess_host());
+        self.send_json(Method::POST, &endpoint, registration).await
+    }
+
+    /// Get an external engine registered by the authenticated user. Requires
+    /// an authenticated client.
+    pub async fn get_external_engine(&self, id: &str) -> Result<ExternalEngine, Box<dyn Error>> {
+        self.require_token()?;
+        self.get_json(&format!("{}/api/external-engine/{}", self.lichess_host(), id)).await
+    }
+
+    /// Replace the settings of an external engine registered by the
+    /// authenticated user. Requires an authenticated client.
+    pub async fn update_external_engine(
+        &self,
+        id: &str,
+        registration: &ExternalEngineRegistration,
+    ) -> Result<ExternalEngine, Box<dyn Error>> {
+        self.require_token()?;
+        let endpoint = format!("{}/api/external-engine/{}", self.lichess_host(), id);
+        self.send_json(Method::PUT, &endpoint, registration).await
+    }
+
+    /// Unregister an external engine registered by the authenticated user.
+    /// Requires an authenticated client.
+    pub async fn delete_external_engine(&self, id: &str) -> Result<(), Box<dyn Error>> {
+        self.require_token()?;
+        let endpoint = format!("{}/api/external-engine/{}", self.lichess_host(), id);
+        hyper::body::to_bytes(self.request(Method::DELETE, &endpoint, Body::empty(), &[]).await?).await?;
+        Ok(())
+    }
+
+    /// Wait for analysis work for any engine registered with the given
+    /// provider secret. Lichess holds the request open for a while if there
+    /// is no work, and returns `None` if none arrives in that time.
+    pub async fn acquire_engine_work(&self, provider_secret: &str) -> Result<Option<EngineWork>, Box<dyn Error>> {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Acquire<'a> {
+            provider_secret: &'a str,
+        }
+
+        let endpoint = format!("{}/api/external-engine/work", self.engine_host());
+        let body = Body::from(serde_json::to_vec(&Acquire { provider_secret })?);
+        let resp = self.post(&endpoint, body, Some("application/json")).await?;
+        let bytes = hyper::body::to_bytes(resp).await?;
+
+        if bytes.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(serde_json::from_slice(&bytes)?))
+        }
+    }
+
+    /// Submit the output of an engine analysing acquired work. The body should
+    /// be the raw UCI output of the engine, one line at a time, and should be
+    /// streamed for as long as the engine is analysing. Lichess closes the
+    /// connection when it no longer wants the analysis.
+    pub async fn submit_engine_work(&self, work_id: &str, body: Body) -> Result<(), Box<dyn Error>> {
+        let endpoint = format!("{}/api/external-engine/work/{}", self.engine_host(), work_id);
+        hyper::body::to_bytes(self.post(&endpoint, body, Some("text/plain")).await?).await?;
+        Ok(())
+    }
+}
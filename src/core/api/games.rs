@@ -0,0 +1,303 @@
+use crate::core::client::{urlencode, Client, ClientError};
+use crate::core::ndjson::ndjson::NDJsonStream;
+use crate::core::pgn::{self, PgnStream};
+use crate::lichess::crosstable::{Crosstable, HeadToHead};
+use crate::lichess::game::{Color, Game, ImportedGame, OngoingGame};
+use crate::lichess::perf_type::PerfType;
+
+use chrono::{DateTime, Utc};
+use futures::stream::{self, Stream, StreamExt};
+use hyper::Body;
+use serde::Deserialize;
+use tokio::time::{sleep_until, Instant};
+
+use std::error::Error;
+use std::time::Duration;
+
+/// The maximum number of ids which may be passed to the game export by ids
+/// endpoint in a single request.
+pub const MAX_EXPORT_IDS: usize = 300;
+
+/// Options for filtering and formatting exported games. Every option is
+/// unset by default, in which case Lichess's own default applies.
+#[derive(Default, Clone, Debug)]
+pub struct GameExportOptions {
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub max: Option<u32>,
+    pub vs: Option<String>,
+    pub rated: Option<bool>,
+    // Games of any perf are exported if empty.
+    pub perf_type: Vec<PerfType>,
+    pub color: Option<Color>,
+    pub analysed: Option<bool>,
+    pub moves: Option<bool>,
+    pub pgn_in_json: Option<bool>,
+    pub clocks: Option<bool>,
+    pub evals: Option<bool>,
+    pub opening: Option<bool>,
+    pub ongoing: Option<bool>,
+    pub finished: Option<bool>,
+}
+
+impl GameExportOptions {
+    /// Encode the options which are set as a query string, without the
+    /// leading question mark.
+    pub(crate) fn query(&self) -> String {
+        let mut params = Vec::new();
+
+        if let Some(since) = self.since {
+            params.push(format!("since={}", since.timestamp_millis()));
+        }
+        if let Some(until) = self.until {
+            params.push(format!("until={}", until.timestamp_millis()));
+        }
+        if let Some(max) = self.max {
+            params.push(format!("max={}", max));
+        }
+        if let Some(vs) = &self.vs {
+            params.push(format!("vs={}", urlencode(vs)));
+        }
+        if !self.perf_type.is_empty() {
+            let perf_types: Vec<&str> = self.perf_type.iter().map(|perf| perf.key()).collect();
+            params.push(format!("perfType={}", perf_types.join(",")));
+        }
+        if let Some(color) = self.color {
+            params.push(format!("color={}", color));
+        }
+
+        let flags = [
+            ("rated", self.rated),
+            ("analysed", self.analysed),
+            ("moves", self.moves),
+            ("pgnInJson", self.pgn_in_json),
+            ("clocks", self.clocks),
+            ("evals", self.evals),
+            ("opening", self.opening),
+            ("ongoing", self.ongoing),
+            ("finished", self.finished),
+        ];
+        for (name, value) in flags {
+            if let Some(value) = value {
+                params.push(format!("{}={}", name, value));
+            }
+        }
+
+        params.join("&")
+    }
+}
+
+impl Client {
+    /// Stream the games played by a user, most recent first.
+    pub async fn export_user_games(
+        &self,
+        username: &str,
+        options: &GameExportOptions,
+    ) -> Result<NDJsonStream<Game>, Box<dyn Error>> {
+        let endpoint = format!(
+            "{}/api/games/user/{}?{}",
+            self.lichess_host(),
+            username,
+            options.query(),
+        );
+        self.get_ndjson(&endpoint).await
+    }
+
+    /// Get the total scores of all games between two users. If `matchup` is
+    /// set, the scores of their current match are included as well.
+    pub async fn get_crosstable(
+        &self,
+        user1: &str,
+        user2: &str,
+        matchup: bool,
+    ) -> Result<Crosstable, Box<dyn Error>> {
+        let endpoint = format!(
+            "{}/api/crosstable/{}/{}?matchup={}",
+            self.lichess_host(), user1, user2, matchup,
+        );
+        self.get_json(&endpoint).await
+    }
+
+    /// Get the head-to-head statistics of a user against an opponent, broken
+    /// down by perf. This streams every game between the two users, so may
+    /// take a while for users who have played each other often.
+    pub async fn get_head_to_head(
+        &self,
+        username: &str,
+        opponent: &str,
+    ) -> Result<HeadToHead, Box<dyn Error>> {
+        let crosstable = self.get_crosstable(username, opponent, false).await?;
+        let mut head_to_head = HeadToHead::new(crosstable);
+
+        let options = GameExportOptions {
+            vs: Some(opponent.to_string()),
+            moves: Some(false),
+            ..GameExportOptions::default()
+        };
+        let mut games = self.export_user_games(username, &options).await?;
+        while let Some(game) = games.next().await {
+            head_to_head.record(username, &game?);
+        }
+
+        Ok(head_to_head)
+    }
+
+    /// Import a single game from PGN. If the client is authenticated, the game
+    /// is attributed to the authenticated user.
+    pub async fn import_game(&self, pgn: &str) -> Result<ImportedGame, Box<dyn Error>> {
+        let endpoint = format!("{}/api/import", self.lichess_host());
+        self.post_form_json(&endpoint, &[("pgn", pgn)]).await
+    }
+
+    /// Import every game in a PGN file containing any number of games, one
+    /// game at a time, waiting at least `interval` between imports. If the
+    /// client is rate limited, importing is suspended until the rate limit is
+    /// lifted. A failure to import one game does not stop the others from
+    /// being imported, so the result of every game is returned, in order.
+    pub async fn import_games(
+        &self,
+        pgn: &str,
+        interval: Duration,
+    ) -> Vec<Result<ImportedGame, Box<dyn Error>>> {
+        let mut results = Vec::new();
+        let mut last_import: Option<Instant> = None;
+
+        for game in pgn::split_games(pgn) {
+            let result = loop {
+                if let Some(last_import) = last_import {
+                    sleep_until(last_import + interval).await;
+                }
+                last_import = Some(Instant::now());
+
+                match self.import_game(&game).await {
+                    Err(e) => match e.downcast_ref::<ClientError>() {
+                        Some(ClientError::RateLimited(until)) => {
+                            sleep_until((*until).into()).await;
+                        }
+                        _ => break Err(e),
+                    },
+                    result => break result,
+                }
+            };
+            results.push(result);
+        }
+
+        results
+    }
+
+    /// Get up to `nb` games being played by the authenticated user, games in
+    /// which it is their turn first. Requires an authenticated client.
+    pub async fn get_ongoing_games(&self, nb: u32) -> Result<Vec<OngoingGame>, Box<dyn Error>> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct NowPlaying {
+            now_playing: Vec<OngoingGame>,
+        }
+
+        self.require_token()?;
+        let endpoint = format!("{}/api/account/playing?nb={}", self.lichess_host(), nb);
+        let now_playing: NowPlaying = self.get_json(&endpoint).await?;
+        Ok(now_playing.now_playing)
+    }
+
+    /// Stream the games bookmarked by the authenticated user. Requires an
+    /// authenticated client.
+    pub async fn export_bookmarked_games(
+        &self,
+        options: &GameExportOptions,
+    ) -> Result<NDJsonStream<Game>, Box<dyn Error>> {
+        self.require_token()?;
+        let endpoint = format!("{}/api/games/export/bookmarks?{}", self.lichess_host(), options.query());
+        self.get_ndjson(&endpoint).await
+    }
+
+    /// Stream the games imported by the authenticated user. Lichess only
+    /// exports imported games as PGN, so each game is yielded as the full
+    /// text of its PGN. Requires an authenticated client.
+    pub async fn export_imported_games(&self) -> Result<PgnStream, Box<dyn Error>> {
+        self.require_token()?;
+        let endpoint = format!("{}/api/games/export/imports", self.lichess_host());
+        self.get_pgn(&endpoint).await
+    }
+
+    /// Stream up to 300 games by their ids, in the order of the ids. Ids of
+    /// games which do not exist are skipped.
+    pub async fn export_games_by_ids(
+        &self,
+        ids: &[&str],
+        options: &GameExportOptions,
+    ) -> Result<NDJsonStream<Game>, Box<dyn Error>> {
+        if ids.len() > MAX_EXPORT_IDS {
+            return Err(Box::new(ClientError::TooManyIds {
+                given: ids.len(),
+                max: MAX_EXPORT_IDS,
+            }));
+        }
+
+        let endpoint = format!("{}/api/games/export/_ids?{}", self.lichess_host(), options.query());
+        self.post_ndjson(&endpoint, Body::from(ids.join(",")), "text/plain").await
+    }
+
+    /// Stream any number of games by their ids, in the order of the ids. The
+    /// ids are split into chunks of at most 300, and each chunk is only
+    /// requested once the games of the previous chunk have all been streamed.
+    /// If the client is rate limited, requesting the next chunk is suspended
+    /// until the rate limit is lifted. If requesting a chunk fails otherwise,
+    /// the error is yielded in place of the games of that chunk, and the
+    /// stream moves on to the next chunk.
+    pub fn export_all_games_by_ids<'a>(
+        &'a self,
+        ids: &'a [&'a str],
+        options: &'a GameExportOptions,
+    ) -> impl Stream<Item = Result<Game, Box<dyn Error>>> + 'a {
+        stream::iter(ids.chunks(MAX_EXPORT_IDS))
+            .then(move |chunk| async move {
+                loop {
+                    match self.export_games_by_ids(chunk, options).await {
+                        Err(e) => match e.downcast_ref::<ClientError>() {
+                            Some(ClientError::RateLimited(until)) => {
+                                sleep_until((*until).into()).await;
+                            }
+                            _ => break Err(e),
+                        },
+                        result => break result,
+                    }
+                }
+            })
+            .flat_map(|result| match result {
+                Ok(games) => games.left_stream(),
+                Err(e) => stream::once(async { Err(e) }).right_stream(),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::TimeZone;
+
+    #[test]
+    fn empty_query() {
+        assert_eq!(GameExportOptions::default().query(), "");
+    }
+
+    #[test]
+    fn query() {
+        let options = GameExportOptions {
+            since: Some(Utc.timestamp_millis_opt(1514505150384).unwrap()),
+            max: Some(10),
+            vs: Some("Mary Jane&co".to_string()),
+            perf_type: vec![PerfType::Blitz, PerfType::KingOfTheHill],
+            color: Some(Color::Black),
+            rated: Some(true),
+            moves: Some(false),
+            ..GameExportOptions::default()
+        };
+        assert_eq!(
+            options.query(),
+            "since=1514505150384&max=10&vs=Mary%20Jane%26co&perfType=blitz,kingOfTheHill\
+             &color=black&rated=true&moves=false"
+        );
+    }
+}
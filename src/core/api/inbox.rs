@@ -0,0 +1,139 @@
+use crate::core::client::{Client, ClientError};
+
+use std::error::Error;
+use std::fmt::Display;
+use std::time::Instant;
+
+use hyper::StatusCode;
+
+impl Client {
+    /// Send a private message to a user. Requires an authenticated client.
+    /// If Lichess refuses to deliver the message, the error is an
+    /// [`InboxError`].
+    pub async fn send_message(&self, username: &str, text: &str) -> Result<(), Box<dyn Error>> {
+        self.require_token()?;
+        let endpoint = format!("{}/inbox/{}", self.lichess_host(), username);
+
+        match self.post_form(&endpoint, &[("text", text)]).await {
+            Ok(body) => {
+                hyper::body::to_bytes(body).await?;
+                Ok(())
+            }
+            Err(e) => match e.downcast_ref::<ClientError>() {
+                Some(ClientError::RateLimited(until)) => {
+                    Err(Box::new(InboxError::RateLimited(Some(*until))))
+                }
+                Some(ClientError::Rejected(status, message)) => {
+                    Err(Box::new(InboxError::from_response(*status, message)))
+                }
+                _ => Err(e),
+            },
+        }
+    }
+}
+
+/// An error in delivering a private message.
+#[derive(Debug)]
+pub enum InboxError {
+    /// The recipient has blocked the sender, or does not accept messages from
+    /// them.
+    Blocked(String),
+    /// The sender has sent too many messages recently. Holds the time until
+    /// which the client refuses further requests, if Lichess responded with a
+    /// rate limit rather than refusing the message for sending too many.
+    RateLimited(Option<Instant>),
+    /// The message was refused for any other reason, such as being empty.
+    Rejected(String),
+}
+
+impl InboxError {
+    /// Classify the response with which Lichess refused a message, by its
+    /// status where that is conclusive, and otherwise by its error message.
+    /// Rate limits are never seen here, since the client reports a 429 status
+    /// as [`ClientError::RateLimited`].
+    fn from_response(status: StatusCode, message: &str) -> Self {
+        match status {
+            StatusCode::FORBIDDEN => InboxError::Blocked(message.to_string()),
+            _ => InboxError::from_message(message),
+        }
+    }
+
+    fn from_message(message: &str) -> Self {
+        let lowercase = message.to_lowercase();
+        if lowercase.contains("block") || lowercase.contains("accept") {
+            InboxError::Blocked(message.to_string())
+        } else if lowercase.contains("limit") || lowercase.contains("too many") {
+            InboxError::RateLimited(None)
+        } else {
+            InboxError::Rejected(message.to_string())
+        }
+    }
+}
+
+impl Display for InboxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InboxError::Blocked(message) => {
+                write!(f, "recipient does not accept messages from the sender: {}", message)
+            }
+            InboxError::RateLimited(Some(until)) => {
+                write!(f, "message was denied due to rate limit in effect until {:?}", until)
+            }
+            InboxError::RateLimited(None) => {
+                write!(f, "message was denied due to too many messages being sent")
+            }
+            InboxError::Rejected(message) => {
+                write!(f, "message was rejected: {}", message)
+            }
+        }
+    }
+}
+
+impl Error for InboxError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn classify(status: StatusCode, message: &str) -> String {
+        match InboxError::from_response(status, message) {
+            InboxError::Blocked(_) => "blocked",
+            InboxError::RateLimited(_) => "rate limited",
+            InboxError::Rejected(_) => "rejected",
+        }
+        .to_string()
+    }
+
+    #[test]
+    fn by_status() {
+        assert_eq!(classify(StatusCode::FORBIDDEN, "The message was rejected"), "blocked");
+        assert_eq!(classify(StatusCode::FORBIDDEN, "Too many messages"), "blocked");
+    }
+
+    #[test]
+    fn by_message() {
+        let bad = StatusCode::BAD_REQUEST;
+        assert_eq!(classify(bad, "This user doesn't accept new messages"), "blocked");
+        assert_eq!(classify(bad, "You are blocked by this user"), "blocked");
+        assert_eq!(classify(bad, "Too many messages. Try again later."), "rate limited");
+        assert_eq!(classify(bad, "Daily message limit reached"), "rate limited");
+        assert_eq!(classify(bad, "The message was rejected"), "rejected");
+        assert_eq!(classify(bad, "text: This field is required"), "rejected");
+    }
+
+    #[test]
+    fn limit_by_message() {
+        match InboxError::from_response(StatusCode::BAD_REQUEST, "Daily message limit reached") {
+            InboxError::RateLimited(until) => assert_eq!(until, None),
+            other => panic!("unexpected {}", other),
+        }
+    }
+
+    #[test]
+    fn keeps_message() {
+        match InboxError::from_response(StatusCode::FORBIDDEN, "You are blocked by this user") {
+            InboxError::Blocked(message) => assert_eq!(message, "You are blocked by this user"),
+            other => panic!("unexpected {}", other),
+        }
+    }
+}
@@ -0,0 +1,11 @@
+pub mod broadcasts;
+pub mod cloud_eval;
+pub mod explorer;
+pub mod external_engine;
+pub mod games;
+pub mod inbox;
+pub mod relations;
+pub mod simuls;
+pub mod studies;
+pub mod tablebase;
+pub mod users;
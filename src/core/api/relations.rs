@@ -0,0 +1,45 @@
+use crate::core::client::Client;
+use crate::core::ndjson::ndjson::NDJsonStream;
+use crate::lichess::user::User;
+
+use hyper::Body;
+
+use std::error::Error;
+
+impl Client {
+    /// Follow a user. Requires an authenticated client.
+    pub async fn follow(&self, username: &str) -> Result<(), Box<dyn Error>> {
+        self.post_relation("follow", username).await
+    }
+
+    /// Unfollow a user. Requires an authenticated client.
+    pub async fn unfollow(&self, username: &str) -> Result<(), Box<dyn Error>> {
+        self.post_relation("unfollow", username).await
+    }
+
+    /// Block a user. Requires an authenticated client.
+    pub async fn block(&self, username: &str) -> Result<(), Box<dyn Error>> {
+        self.post_relation("block", username).await
+    }
+
+    /// Unblock a user. Requires an authenticated client.
+    pub async fn unblock(&self, username: &str) -> Result<(), Box<dyn Error>> {
+        self.post_relation("unblock", username).await
+    }
+
+    /// Stream the users followed by the authenticated user. Requires an
+    /// authenticated client.
+    pub async fn get_following(&self) -> Result<NDJsonStream<User>, Box<dyn Error>> {
+        self.require_token()?;
+        self.get_ndjson(&format!("{}/api/rel/following", self.lichess_host())).await
+    }
+
+    /// All of the relation endpoints share the same shape, differing only in
+    /// the action in the path.
+    async fn post_relation(&self, action: &str, username: &str) -> Result<(), Box<dyn Error>> {
+        self.require_token()?;
+        let endpoint = format!("{}/api/rel/{}/{}", self.lichess_host(), action, username);
+        hyper::body::to_bytes(self.post(&endpoint, Body::empty(), None).await?).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,12 @@
+use crate::core::client::Client;
+use crate::lichess::simul::Simuls;
+
+use std::error::Error;
+
+impl Client {
+    /// Get the simuls which are pending, recently created, running, or
+    /// recently finished.
+    pub async fn get_simuls(&self) -> Result<Simuls, Box<dyn Error>> {
+        self.get_json(&format!("{}/api/simul", self.lichess_host())).await
+    }
+}
@@ -0,0 +1,122 @@
+use crate::core::client::Client;
+use crate::core::ndjson::ndjson::NDJsonStream;
+use crate::lichess::game::Color;
+use crate::lichess::study::{ImportedChapter, ImportedChapters, StudyMetadata};
+use crate::lichess::variant::Variant;
+
+use chrono::{DateTime, Utc};
+use hyper::header::LAST_MODIFIED;
+use hyper::{Body, Method};
+
+use std::error::Error;
+
+/// Options for exporting studies as PGN. Every option is unset by default, in
+/// which case Lichess's own default applies.
+#[derive(Default, Clone, Debug)]
+pub struct StudyExportOptions {
+    pub clocks: Option<bool>,
+    pub comments: Option<bool>,
+    pub variations: Option<bool>,
+    pub orientation: Option<bool>,
+}
+
+impl StudyExportOptions {
+    /// Encode the options which are set as a query string, without the
+    /// leading question mark.
+    fn query(&self) -> String {
+        let flags = [
+            ("clocks", self.clocks),
+            ("comments", self.comments),
+            ("variations", self.variations),
+            ("orientation", self.orientation),
+        ];
+        let params: Vec<String> = flags
+            .iter()
+            .filter_map(|(name, value)| value.map(|value| format!("{}={}", name, value)))
+            .collect();
+        params.join("&")
+    }
+}
+
+/// Options for the chapters created by importing PGN into a study. Chapters
+/// are named after the players of each game unless a name is given.
+#[derive(Default, Clone, Debug)]
+pub struct StudyImportOptions {
+    pub name: Option<String>,
+    pub orientation: Option<Color>,
+    pub variant: Option<Variant>,
+}
+
+impl Client {
+    /// Export every chapter of a study as PGN.
+    pub async fn export_study(&self, study_id: &str, options: &StudyExportOptions) -> Result<String, Box<dyn Error>> {
+        let endpoint = format!("{}/api/study/{}.pgn?{}", self.lichess_host(), study_id, options.query());
+        self.get_text(&endpoint).await
+    }
+
+    /// Export a single chapter of a study as PGN.
+    pub async fn export_study_chapter(
+        &self,
+        study_id: &str,
+        chapter_id: &str,
+        options: &StudyExportOptions,
+    ) -> Result<String, Box<dyn Error>> {
+        let endpoint = format!(
+            "{}/api/study/{}/{}.pgn?{}",
+            self.lichess_host(),
+            study_id,
+            chapter_id,
+            options.query(),
+        );
+        self.get_text(&endpoint).await
+    }
+
+    /// Get the time at which a study was last modified, without downloading
+    /// it. Useful for checking whether a previously exported study is stale.
+    pub async fn get_study_last_modified(&self, study_id: &str) -> Result<Option<DateTime<Utc>>, Box<dyn Error>> {
+        let endpoint = format!("{}/api/study/{}.pgn", self.lichess_host(), study_id);
+        let resp = self.send_request(Method::HEAD, &endpoint, Body::empty(), &[]).await?;
+
+        match resp.headers().get(LAST_MODIFIED) {
+            Some(value) => {
+                let date = DateTime::parse_from_rfc2822(value.to_str()?)?;
+                Ok(Some(date.with_timezone(&Utc)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Stream the metadata of the studies created by a user. Private studies
+    /// are only included if the client is authenticated as the user.
+    pub async fn list_user_studies(&self, username: &str) -> Result<NDJsonStream<StudyMetadata>, Box<dyn Error>> {
+        self.get_ndjson(&format!("{}/api/study/by/{}", self.lichess_host(), username)).await
+    }
+
+    /// Import PGN into a study, creating one chapter per game in the PGN, and
+    /// return the created chapters. Requires an authenticated client with
+    /// permission to contribute to the study.
+    pub async fn import_study_pgn(
+        &self,
+        study_id: &str,
+        pgn: &str,
+        options: &StudyImportOptions,
+    ) -> Result<Vec<ImportedChapter>, Box<dyn Error>> {
+        self.require_token()?;
+
+        let orientation = options.orientation.map(|color| color.to_string());
+        let mut fields = vec![("pgn", pgn)];
+        if let Some(name) = &options.name {
+            fields.push(("name", name));
+        }
+        if let Some(orientation) = &orientation {
+            fields.push(("orientation", orientation));
+        }
+        if let Some(variant) = options.variant {
+            fields.push(("variant", variant.key()));
+        }
+
+        let endpoint = format!("{}/api/study/{}/import-pgn", self.lichess_host(), study_id);
+        let imported: ImportedChapters = self.post_form_json(&endpoint, &fields).await?;
+        Ok(imported.chapters)
+    }
+}
@@ -0,0 +1,31 @@
+use crate::core::client::{urlencode, Client};
+use crate::lichess::tablebase::TablebaseResult;
+
+use std::error::Error;
+
+impl Client {
+    /// Look up a standard chess position with up to 7 pieces in the endgame
+    /// tablebase.
+    pub async fn tablebase_standard(&self, fen: &str) -> Result<TablebaseResult, Box<dyn Error>> {
+        self.get_tablebase("standard", fen).await
+    }
+
+    /// Look up an atomic chess position with up to 6 pieces in the endgame
+    /// tablebase.
+    pub async fn tablebase_atomic(&self, fen: &str) -> Result<TablebaseResult, Box<dyn Error>> {
+        self.get_tablebase("atomic", fen).await
+    }
+
+    /// Look up an antichess position with up to 6 pieces in the endgame
+    /// tablebase.
+    pub async fn tablebase_antichess(&self, fen: &str) -> Result<TablebaseResult, Box<dyn Error>> {
+        self.get_tablebase("antichess", fen).await
+    }
+
+    /// All of the tablebase endpoints share the same shape, differing only in
+    /// the variant in the path.
+    async fn get_tablebase(&self, variant: &str, fen: &str) -> Result<TablebaseResult, Box<dyn Error>> {
+        let endpoint = format!("{}/{}?fen={}", self.tablebase_host(), variant, urlencode(fen));
+        self.get_json(&endpoint).await
+    }
+}
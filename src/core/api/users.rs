@@ -0,0 +1,59 @@
+use crate::core::client::{Client, ClientError};
+use crate::lichess::activity::Activity;
+use crate::lichess::perf_stat::PerfStat;
+use crate::lichess::perf_type::PerfType;
+use crate::lichess::rating_history::RatingHistory;
+use crate::lichess::status::UserStatus;
+
+use std::error::Error;
+
+/// The maximum number of ids which may be passed to the user status endpoint
+/// in a single request.
+pub(crate) const MAX_STATUS_IDS: usize = 100;
+
+impl Client {
+    /// Get the real-time status of up to 100 users. If `with_signal` is set,
+    /// the network signal of online users is included, and if
+    /// `with_game_ids` is set, the id of the game being played by playing
+    /// users is included.
+    pub async fn get_user_statuses(
+        &self,
+        ids: &[&str],
+        with_signal: bool,
+        with_game_ids: bool,
+    ) -> Result<Vec<UserStatus>, Box<dyn Error>> {
+        if ids.len() > MAX_STATUS_IDS {
+            return Err(Box::new(ClientError::TooManyIds {
+                given: ids.len(),
+                max: MAX_STATUS_IDS,
+            }));
+        }
+
+        let endpoint = format!(
+            "{}/api/users/status?ids={}&withSignal={}&withGameIds={}",
+            self.lichess_host(),
+            ids.join(","),
+            with_signal,
+            with_game_ids,
+        );
+        self.get_json(&endpoint).await
+    }
+
+    /// Get the activity feed of a user, most recent interval first.
+    pub async fn get_user_activity(&self, username: &str) -> Result<Vec<Activity>, Box<dyn Error>> {
+        let endpoint = format!("{}/api/user/{}/activity", self.lichess_host(), username);
+        self.get_json(&endpoint).await
+    }
+
+    /// Get detailed statistics of a user's performance in a single perf.
+    pub async fn get_user_perf_stat(&self, username: &str, perf: PerfType) -> Result<PerfStat, Box<dyn Error>> {
+        let endpoint = format!("{}/api/user/{}/perf/{}", self.lichess_host(), username, perf);
+        self.get_json(&endpoint).await
+    }
+
+    /// Get the rating history of a user in every perf.
+    pub async fn get_rating_history(&self, username: &str) -> Result<RatingHistory, Box<dyn Error>> {
+        let endpoint = format!("{}/api/user/{}/rating-history", self.lichess_host(), username);
+        self.get_json(&endpoint).await
+    }
+}
@@ -0,0 +1,296 @@
+use hyper::body::{Bytes, HttpBody};
+use hyper::{Body, Method, Response, StatusCode};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// A recording of the requests a [`Client`](crate::core::client::Client)
+/// made and the responses it received, in the order the requests were made.
+/// Cassettes are stored as JSON, so that they can be inspected and edited by
+/// hand.
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+pub struct Cassette {
+    pub interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    /// Read a cassette from a file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn Error>> {
+        Ok(serde_json::from_reader(File::open(path)?)?)
+    }
+
+    /// Write the cassette to a file, replacing it if it exists.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn Error>> {
+        Ok(self.write(path.as_ref())?)
+    }
+
+    fn write(&self, path: &Path) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(&mut writer, self)?;
+        writer.flush()
+    }
+}
+
+/// A request and the response it received. Request headers are never
+/// recorded, so cassettes recorded by an authenticated client do not contain
+/// its token. Bodies are stored as text if they are valid UTF-8, and as an
+/// array of bytes otherwise, so that binary bodies and chunks which split a
+/// multibyte character are stored exactly.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Interaction {
+    pub method: String,
+    pub uri: String,
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "serialize_data",
+        deserialize_with = "deserialize_data"
+    )]
+    pub request_body: Vec<u8>,
+    pub status: u16,
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+    /// The response body, in the chunks in which it was received. Streamed
+    /// responses, such as NDJSON, usually arrive in many chunks.
+    pub chunks: Vec<Chunk>,
+    /// The error which cut the response body short, such as the connection
+    /// being dropped. Replaying the interaction fails its body the same way
+    /// once the recorded chunks are sent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// A chunk of a response body, with the time which passed between it and the
+/// previous chunk, or the response headers for the first chunk.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Chunk {
+    pub delay_ms: u64,
+    #[serde(serialize_with = "serialize_data", deserialize_with = "deserialize_data")]
+    pub data: Vec<u8>,
+}
+
+fn serialize_data<S>(data: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match std::str::from_utf8(data) {
+        Ok(text) => serializer.serialize_str(text),
+        Err(_) => data.serialize(serializer),
+    }
+}
+
+fn deserialize_data<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Data {
+        Text(String),
+        Bytes(Vec<u8>),
+    }
+
+    Ok(match Data::deserialize(deserializer)? {
+        Data::Text(text) => text.into_bytes(),
+        Data::Bytes(bytes) => bytes,
+    })
+}
+
+/// Records the interactions of a client, writing the cassette to its file as
+/// each response body completes.
+pub(crate) struct Recorder {
+    path: PathBuf,
+    cassette: Mutex<Cassette>,
+    // The tasks recording response bodies, which may still be running.
+    tasks: Mutex<Vec<JoinHandle<()>>>,
+    // Held while the file is written, so that an older snapshot of the
+    // cassette cannot overwrite a newer one.
+    saving: tokio::sync::Mutex<()>,
+}
+
+impl Recorder {
+    /// Start recording to the given file, which is created empty immediately
+    /// so that an unwritable path is reported up front.
+    pub(crate) fn new(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let cassette = Cassette::default();
+        cassette.save(path)?;
+        Ok(Recorder {
+            path: path.to_path_buf(),
+            cassette: Mutex::new(cassette),
+            tasks: Mutex::new(Vec::new()),
+            saving: tokio::sync::Mutex::new(()),
+        })
+    }
+
+    /// Wait for every response body recorded so far to complete, and write
+    /// the cassette to its file.
+    pub(crate) async fn flush(&self) -> Result<(), Box<dyn Error>> {
+        let tasks = std::mem::take(&mut *self.tasks.lock().unwrap());
+        for task in tasks {
+            task.await?;
+        }
+        Ok(self.save().await?)
+    }
+
+    /// Write a snapshot of the cassette to its file, on a thread where
+    /// blocking is allowed.
+    async fn save(&self) -> io::Result<()> {
+        let _saving = self.saving.lock().await;
+        let cassette = self.cassette.lock().unwrap().clone();
+        let path = self.path.clone();
+        tokio::task::spawn_blocking(move || cassette.write(&path)).await?
+    }
+
+    /// Record an interaction, and return a response which forwards the body
+    /// of the given response as it arrives.
+    pub(crate) fn record(
+        self: &Arc<Self>,
+        method: &Method,
+        uri: &str,
+        request_body: &[u8],
+        resp: Response<Body>,
+    ) -> Response<Body> {
+        let (parts, mut body) = resp.into_parts();
+
+        // The interaction is added before its body arrives, so that the
+        // cassette lists interactions in the order requests were made even
+        // when the bodies of concurrent responses complete out of order.
+        let index = {
+            let mut cassette = self.cassette.lock().unwrap();
+            cassette.interactions.push(Interaction {
+                method: method.to_string(),
+                uri: uri.to_string(),
+                request_body: request_body.to_vec(),
+                status: parts.status.as_u16(),
+                headers: parts
+                    .headers
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), String::from_utf8_lossy(value.as_bytes()).into_owned()))
+                    .collect(),
+                chunks: Vec::new(),
+                error: None,
+            });
+            cassette.interactions.len() - 1
+        };
+
+        let (mut sender, forwarded) = Body::channel();
+        let recorder = self.clone();
+        let task = tokio::spawn(async move {
+            let mut chunks = Vec::new();
+            let mut error = None;
+            let mut last = Instant::now();
+            // The whole body is recorded even if the caller stops reading it,
+            // as happens with the bodies of most error responses.
+            let mut forwarding = true;
+            loop {
+                match body.data().await {
+                    Some(Ok(data)) => {
+                        let now = Instant::now();
+                        chunks.push(Chunk {
+                            delay_ms: now.duration_since(last).as_millis() as u64,
+                            data: data.to_vec(),
+                        });
+                        last = now;
+                        if forwarding {
+                            forwarding = sender.send_data(data).await.is_ok();
+                        }
+                    }
+                    Some(Err(e)) => {
+                        error = Some(e.to_string());
+                        // The caller must not mistake the truncated body for
+                        // a complete one.
+                        sender.abort();
+                        break;
+                    }
+                    None => {
+                        drop(sender);
+                        break;
+                    }
+                }
+            }
+
+            {
+                let mut cassette = recorder.cassette.lock().unwrap();
+                let interaction = &mut cassette.interactions[index];
+                interaction.chunks = chunks;
+                interaction.error = error;
+            }
+            // There is no caller to report a failure to at this point, but
+            // flushing saves again and reports any failure then.
+            recorder.save().await.ok();
+        });
+
+        let mut tasks = self.tasks.lock().unwrap();
+        tasks.retain(|task| !task.is_finished());
+        tasks.push(task);
+
+        Response::from_parts(parts, forwarded)
+    }
+}
+
+/// Serves the responses of a cassette in place of a server.
+pub(crate) struct Player {
+    interactions: Vec<Interaction>,
+    played: Mutex<Vec<bool>>,
+    realtime: bool,
+}
+
+impl Player {
+    pub(crate) fn new(cassette: Cassette, realtime: bool) -> Self {
+        Player {
+            played: Mutex::new(vec![false; cassette.interactions.len()]),
+            interactions: cassette.interactions,
+            realtime,
+        }
+    }
+
+    /// The response to the first interaction with the given method and URI
+    /// which has not been played yet, so that repeated requests are served
+    /// their recorded responses in order.
+    pub(crate) fn play(&self, method: &Method, uri: &str) -> Option<Response<Body>> {
+        let mut played = self.played.lock().unwrap();
+        let index = self.interactions.iter().zip(played.iter()).position(|(interaction, played)| {
+            !played && interaction.method == method.as_str() && interaction.uri == uri
+        })?;
+        played[index] = true;
+
+        let interaction = &self.interactions[index];
+        let realtime = self.realtime;
+        let chunks: Vec<(Duration, Bytes)> = interaction
+            .chunks
+            .iter()
+            .map(|chunk| (Duration::from_millis(chunk.delay_ms), Bytes::from(chunk.data.clone())))
+            .collect();
+        let failed = interaction.error.is_some();
+
+        let (mut sender, body) = Body::channel();
+        tokio::spawn(async move {
+            for (delay, data) in chunks {
+                if realtime && !delay.is_zero() {
+                    tokio::time::sleep(delay).await;
+                }
+                if sender.send_data(data).await.is_err() {
+                    return;
+                }
+            }
+            if failed {
+                sender.abort();
+            }
+        });
+
+        let mut builder = Response::builder().status(StatusCode::from_u16(interaction.status).ok()?);
+        for (name, value) in &interaction.headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+        builder.body(body).ok()
+    }
+}
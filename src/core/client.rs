@@ -1,22 +1,62 @@
+use crate::core::cassette::{Cassette, Player, Recorder};
 use crate::core::ndjson::ndjson::NDJsonStream;
+use crate::core::pgn::PgnStream;
 
-use hyper::{ Request, Method, Body, StatusCode };
+use hyper::{ Request, Response, Method, Body, StatusCode, Uri };
+use hyper::header::{HeaderName, ACCEPT, AUTHORIZATION, CONTENT_TYPE};
 use hyper::client::HttpConnector;
 use hyper_tls::HttpsConnector;
 
+use std::collections::HashMap;
 use std::time::{Duration, Instant};
 use std::error::Error;
 use std::fmt::Display;
+use std::path::Path;
+use std::sync::Arc;
 
-use crossbeam::atomic::AtomicCell;
 use tokio::sync::Mutex;
 
 use serde::de::DeserializeOwned;
+use serde::Serialize;
 
-/// A client connected to the Lichess API. This client is not authenticated.
+/// The default base URL of the Lichess API.
+const LICHESS_HOST: &str = "https://lichess.org";
+
+/// The default base URL of the Lichess opening explorer.
+pub(crate) const EXPLORER_HOST: &str = "https://explorer.lichess.ovh";
+
+/// The default base URL of the Lichess endgame tablebase.
+pub(crate) const TABLEBASE_HOST: &str = "https://tablebase.lichess.ovh";
+
+/// The default base URL of the Lichess external engine provider API.
+pub(crate) const ENGINE_HOST: &str = "https://engine.lichess.ovh";
+
+/// The MIME type of NDJSON.
+const NDJSON: &str = "application/x-ndjson";
+
+/// A client connected to the Lichess API. A client created with
+/// [`Client::new`] is not authenticated, and can only access public endpoints.
+/// A client created with [`Client::with_token`] authenticates every request
+/// with a personal API access token.
 pub struct Client {
     http_client: Mutex<hyper::Client<HttpsConnector<HttpConnector>>>,
-    rate_limiter: AtomicCell<Option<Instant>>,
+    // The time until which each host is known to be rate limiting the
+    // client, keyed by the host's authority. Hosts are limited independently,
+    // so that a 429 from the opening explorer does not block the main API.
+    rate_limits: std::sync::Mutex<HashMap<String, Instant>>,
+    token: Option<String>,
+    lichess_host: String,
+    explorer_host: String,
+    tablebase_host: String,
+    engine_host: String,
+    cassette: Option<CassetteMode>,
+}
+
+/// Whether a client records its interactions to a cassette or replays them
+/// from one.
+enum CassetteMode {
+    Recording(Arc<Recorder>),
+    Replaying(Player),
 }
 
 impl Client {
@@ -25,61 +65,376 @@ impl Client {
             http_client: Mutex::new(
                 hyper::Client::builder().build(HttpsConnector::new()),
             ),
-            rate_limiter: AtomicCell::new(None),
+            rate_limits: std::sync::Mutex::new(HashMap::new()),
+            token: None,
+            lichess_host: LICHESS_HOST.to_string(),
+            explorer_host: EXPLORER_HOST.to_string(),
+            tablebase_host: TABLEBASE_HOST.to_string(),
+            engine_host: ENGINE_HOST.to_string(),
+            cassette: None,
         }
     }
 
-    /// Send a GET request to the supplied endpoint, then return the response
-    /// body. Requests cannot be made synchonously, and will error if the
-    /// server responds with a 429 status code, in which case new requests will
-    /// all error for 60 seconds, or until the rate limit is lifted.
-    async fn get(&self, endpoint: &str) -> Result<Body, Box<dyn Error>> {
-        if let Some(rate_limiter) = self.rate_limiter.load() {
-            if rate_limiter >= Instant::now() {
-                return Err(Box::new(ClientError::RateLimited(rate_limiter)));
-            } else {
-                self.rate_limiter.store(None);
-            }
+    /// Create a client which authenticates every request with the supplied
+    /// personal API access token.
+    pub fn with_token(token: &str) -> Self {
+        Client {
+            token: Some(token.to_string()),
+            ..Client::new()
         }
+    }
 
-        let req = Request::builder()
-            .method(Method::GET)
-            .uri(endpoint)
-            .body(Body::empty())
-            .unwrap();
+    /// Direct Lichess API requests to a different host, such as a local
+    /// instance of [lila][1] or a mock server. The host should include the
+    /// scheme, and no trailing slash.
+    /// [1]: <https://github.com/lichess-org/lila>
+    pub fn set_lichess_host(&mut self, host: &str) {
+        self.lichess_host = host.to_string();
+    }
 
-        let http_client = self.http_client.lock().await;
+    /// The base URL to which Lichess API requests are sent.
+    pub(crate) fn lichess_host(&self) -> &str {
+        &self.lichess_host
+    }
+
+    /// Direct opening explorer requests to a different host, such as a local
+    /// instance of [lila-openingexplorer][1] or a mock server. The host should
+    /// include the scheme, and no trailing slash.
+    /// [1]: <https://github.com/lichess-org/lila-openingexplorer>
+    pub fn set_explorer_host(&mut self, host: &str) {
+        self.explorer_host = host.to_string();
+    }
+
+    /// The base URL to which opening explorer requests are sent.
+    pub(crate) fn explorer_host(&self) -> &str {
+        &self.explorer_host
+    }
+
+    /// Direct endgame tablebase requests to a different host, such as a local
+    /// instance of [lila-tablebase][1] or a mock server. The host should
+    /// include the scheme, and no trailing slash.
+    /// [1]: <https://github.com/lichess-org/lila-tablebase>
+    pub fn set_tablebase_host(&mut self, host: &str) {
+        self.tablebase_host = host.to_string();
+    }
+
+    /// The base URL to which endgame tablebase requests are sent.
+    pub(crate) fn tablebase_host(&self) -> &str {
+        &self.tablebase_host
+    }
+
+    /// Direct external engine provider requests to a different host, such as
+    /// a mock server. The host should include the scheme, and no trailing
+    /// slash.
+    pub fn set_engine_host(&mut self, host: &str) {
+        self.engine_host = host.to_string();
+    }
 
-        let resp = http_client.request(req).await?;
+    /// The base URL to which external engine provider requests are sent.
+    pub(crate) fn engine_host(&self) -> &str {
+        &self.engine_host
+    }
+
+    /// Record every request the client makes and the response it receives,
+    /// including the timing of the chunks of streamed responses, to a
+    /// [`Cassette`] at the given path. The file is replaced, and rewritten as
+    /// each response body completes. Bodies are recorded in the background,
+    /// so call [`Client::flush_cassette`] before relying on the file.
+    pub fn record_cassette(&mut self, path: impl AsRef<Path>) -> Result<(), Box<dyn Error>> {
+        self.cassette = Some(CassetteMode::Recording(Arc::new(Recorder::new(path.as_ref())?)));
+        Ok(())
+    }
+
+    /// Wait for the body of every response recorded so far to complete, and
+    /// write the cassette to its file, returning any error in doing so. A
+    /// body which is neither read to its end nor dropped is waited for
+    /// indefinitely. Does nothing if the client is not recording.
+    pub async fn flush_cassette(&self) -> Result<(), Box<dyn Error>> {
+        match &self.cassette {
+            Some(CassetteMode::Recording(recorder)) => recorder.flush().await,
+            _ => Ok(()),
+        }
+    }
+
+    /// Serve every request from the [`Cassette`] at the given path instead of
+    /// the network. Requests are matched to recorded interactions by method
+    /// and URI, and fail with [`ClientError::NotRecorded`] if none match. If
+    /// `realtime` is set, the chunks of each response are delayed as they
+    /// were when recorded, and otherwise they are all available at once.
+    pub fn replay_cassette(&mut self, path: impl AsRef<Path>, realtime: bool) -> Result<(), Box<dyn Error>> {
+        self.cassette = Some(CassetteMode::Replaying(Player::new(Cassette::load(path)?, realtime)));
+        Ok(())
+    }
+
+    /// Return an error if the client is not authenticated. Endpoints which
+    /// require authentication should call this before making any request.
+    pub(crate) fn require_token(&self) -> Result<(), ClientError> {
+        match self.token {
+            Some(_) => Ok(()),
+            None => Err(ClientError::Unauthenticated),
+        }
+    }
+
+    /// Send a request to the supplied endpoint, then return the response.
+    /// Requests cannot be made synchonously, and will error if the server
+    /// responds with a 429 status code, in which case new requests to the same
+    /// host will all error for 60 seconds, or until the rate limit is lifted.
+    pub(crate) async fn send_request(
+        &self,
+        method: Method,
+        endpoint: &str,
+        body: Body,
+        headers: &[(HeaderName, &str)],
+    ) -> Result<Response<Body>, Box<dyn Error>> {
+        let host = rate_limit_key(endpoint);
+        {
+            let mut rate_limits = self.rate_limits.lock().unwrap();
+            if let Some(&until) = rate_limits.get(&host) {
+                if until > now() {
+                    return Err(Box::new(ClientError::RateLimited(until)));
+                }
+                rate_limits.remove(&host);
+            }
+        }
+
+        let resp = match &self.cassette {
+            Some(CassetteMode::Replaying(player)) => player
+                .play(&method, endpoint)
+                .ok_or_else(|| ClientError::NotRecorded(method, endpoint.to_string()))?,
+            Some(CassetteMode::Recording(recorder)) => {
+                // The request body is buffered so that it can be both
+                // recorded and sent.
+                let request_body = hyper::body::to_bytes(body).await?;
+                let resp = self
+                    .send_http(method.clone(), endpoint, Body::from(request_body.clone()), headers)
+                    .await?;
+                recorder.record(&method, endpoint, &request_body, resp)
+            }
+            None => self.send_http(method, endpoint, body, headers).await?,
+        };
 
         match resp.status() {
-            StatusCode::OK => {
-                Ok(resp.into_body())
+            status if status.is_success() => {
+                Ok(resp)
             }
             StatusCode::TOO_MANY_REQUESTS => {
-                let new_rate_limiter = Instant::now() + Duration::from_secs(60);
-                self.rate_limiter.store(Some(new_rate_limiter));
-                Err(Box::new(ClientError::RateLimited(new_rate_limiter)))
+                let until = now() + Duration::from_secs(60);
+                self.rate_limits.lock().unwrap().insert(host, until);
+                Err(Box::new(ClientError::RateLimited(until)))
+            }
+            StatusCode::UNAUTHORIZED => {
+                Err(Box::new(ClientError::Unauthenticated))
+            }
+            status @ (StatusCode::BAD_REQUEST | StatusCode::FORBIDDEN) => {
+                let bytes = hyper::body::to_bytes(resp.into_body()).await?;
+                Err(Box::new(ClientError::Rejected(status, error_message(&bytes))))
             }
             other => {
-                panic!("Invalid response status code: {}", other);
+                Err(Box::new(ClientError::UnexpectedStatus(other)))
             }
         }
     }
 
+    /// Send a request over the network, and return the response whatever its
+    /// status.
+    async fn send_http(
+        &self,
+        method: Method,
+        endpoint: &str,
+        body: Body,
+        headers: &[(HeaderName, &str)],
+    ) -> Result<Response<Body>, Box<dyn Error>> {
+        let mut builder = Request::builder().method(method).uri(endpoint);
+        if let Some(token) = &self.token {
+            builder = builder.header(AUTHORIZATION, format!("Bearer {}", token));
+        }
+        for (name, value) in headers {
+            builder = builder.header(name, *value);
+        }
+        let req = builder.body(body)?;
+
+        let http_client = self.http_client.lock().await;
+
+        Ok(http_client.request(req).await?)
+    }
+
+    /// Send a request to the supplied endpoint, then return the response
+    /// body.
+    pub(crate) async fn request(
+        &self,
+        method: Method,
+        endpoint: &str,
+        body: Body,
+        headers: &[(HeaderName, &str)],
+    ) -> Result<Body, Box<dyn Error>> {
+        Ok(self.send_request(method, endpoint, body, headers).await?.into_body())
+    }
+
+    /// Send a GET request to the supplied endpoint, then return the response
+    /// body.
+    async fn get(&self, endpoint: &str) -> Result<Body, Box<dyn Error>> {
+        self.request(Method::GET, endpoint, Body::empty(), &[]).await
+    }
+
+    /// Send a POST request with the supplied body to the supplied endpoint,
+    /// then return the response body.
+    pub(crate) async fn post(
+        &self,
+        endpoint: &str,
+        body: Body,
+        content_type: Option<&str>,
+    ) -> Result<Body, Box<dyn Error>> {
+        match content_type {
+            Some(content_type) => {
+                self.request(Method::POST, endpoint, body, &[(CONTENT_TYPE, content_type)]).await
+            }
+            None => self.request(Method::POST, endpoint, body, &[]).await,
+        }
+    }
+
+    /// Send a POST request with the supplied fields as a URL-encoded form body
+    /// to the supplied endpoint, then return the response body.
+    pub(crate) async fn post_form<V: AsRef<str>>(
+        &self,
+        endpoint: &str,
+        fields: &[(&str, V)],
+    ) -> Result<Body, Box<dyn Error>> {
+        let form: Vec<String> = fields
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, urlencode(value.as_ref())))
+            .collect();
+        self.post(endpoint, Body::from(form.join("&")), Some("application/x-www-form-urlencoded"))
+            .await
+    }
+
+    /// Send a POST request with the supplied fields as a URL-encoded form body,
+    /// and deserialize the response as a JSON object.
+    pub(crate) async fn post_form_json<V, T>(&self, endpoint: &str, fields: &[(&str, V)]) -> Result<T, Box<dyn Error>>
+    where
+        V: AsRef<str>,
+        T: DeserializeOwned,
+    {
+        let bytes = hyper::body::to_bytes(self.post_form(endpoint, fields).await?).await?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    /// Send a request with the supplied value serialized as a JSON body, and
+    /// deserialize the response as a JSON object.
+    pub(crate) async fn send_json<B, T>(
+        &self,
+        method: Method,
+        endpoint: &str,
+        body: &B,
+    ) -> Result<T, Box<dyn Error>>
+    where
+        B: Serialize,
+        T: DeserializeOwned,
+    {
+        let body = Body::from(serde_json::to_vec(body)?);
+        let resp = self
+            .request(method, endpoint, body, &[(CONTENT_TYPE, "application/json")])
+            .await?;
+        let bytes = hyper::body::to_bytes(resp).await?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
     /// Make a GET request to the API, and deserialize the response as a JSON
     /// object.
-    async fn get_json<T>(&self, endpoint: &str) -> Result<T, Box<dyn Error>>
+    pub(crate) async fn get_json<T>(&self, endpoint: &str) -> Result<T, Box<dyn Error>>
     where T: DeserializeOwned
     {
         let bytes = hyper::body::to_bytes(self.get(endpoint).await?).await?;
         Ok(serde_json::from_slice(&bytes)?)
     }
 
+    /// Make a GET request to the API, and return the response as text, such
+    /// as PGN.
+    pub(crate) async fn get_text(&self, endpoint: &str) -> Result<String, Box<dyn Error>> {
+        let bytes = hyper::body::to_bytes(self.get(endpoint).await?).await?;
+        Ok(String::from_utf8(bytes.to_vec())?)
+    }
+
+    /// Send a POST request with the supplied body to the API, and deserialize
+    /// the response as an NDJSON stream.
+    pub(crate) async fn post_ndjson<T>(
+        &self,
+        endpoint: &str,
+        body: Body,
+        content_type: &str,
+    ) -> Result<NDJsonStream<T>, Box<dyn Error>> {
+        let body = self
+            .request(Method::POST, endpoint, body, &[(ACCEPT, NDJSON), (CONTENT_TYPE, content_type)])
+            .await?;
+        Ok(NDJsonStream::new(body))
+    }
+
+    /// Make a GET request to the API, and split the response into a stream of
+    /// PGN games.
+    pub(crate) async fn get_pgn(&self, endpoint: &str) -> Result<PgnStream, Box<dyn Error>> {
+        Ok(PgnStream::new(self.get(endpoint).await?))
+    }
+
     /// Make a GET request to the API, and deserialize the response as an
-    /// NDJSON stream.
-    async fn get_ndjson<T>(&self, endpoint: &str) -> Result<NDJsonStream<T>, Box<dyn Error>> {
-        Ok(NDJsonStream::new(self.get(endpoint).await?))
+    /// NDJSON stream. Some endpoints respond with other formats unless NDJSON
+    /// is explicitly accepted, so the Accept header is always set.
+    pub(crate) async fn get_ndjson<T>(&self, endpoint: &str) -> Result<NDJsonStream<T>, Box<dyn Error>> {
+        let body = self
+            .request(Method::GET, endpoint, Body::empty(), &[(ACCEPT, NDJSON)])
+            .await?;
+        Ok(NDJsonStream::new(body))
+    }
+}
+
+/// Extract the error message from the body of a rejected request. Lichess
+/// usually responds with a JSON object with an error field, but occasionally
+/// with plain text.
+fn error_message(body: &[u8]) -> String {
+    #[derive(serde::Deserialize)]
+    struct ErrorBody {
+        error: serde_json::Value,
+    }
+
+    match serde_json::from_slice::<ErrorBody>(body) {
+        Ok(ErrorBody { error: serde_json::Value::String(message) }) => message,
+        Ok(ErrorBody { error }) => error.to_string(),
+        Err(_) => String::from_utf8_lossy(body).trim().to_string(),
+    }
+}
+
+/// The key under which the rate limit of the host of an endpoint is tracked,
+/// which is its authority, such as "lichess.org".
+fn rate_limit_key(endpoint: &str) -> String {
+    endpoint
+        .parse::<Uri>()
+        .ok()
+        .and_then(|uri| uri.authority().map(|authority| authority.to_string()))
+        .unwrap_or_default()
+}
+
+/// The current time by Tokio's clock, against which rate limits are measured
+/// so that they can be waited out with the clock paused.
+fn now() -> Instant {
+    tokio::time::Instant::now().into_std()
+}
+
+/// Percent-encode a string for use as a value in a query string or a form
+/// body. Only unreserved characters are left unencoded.
+pub(crate) fn urlencode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char);
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -87,6 +442,11 @@ impl Client {
 #[derive(Debug)]
 pub enum ClientError {
     RateLimited(Instant),
+    TooManyIds { given: usize, max: usize },
+    Unauthenticated,
+    Rejected(StatusCode, String),
+    UnexpectedStatus(StatusCode),
+    NotRecorded(Method, String),
 }
 
 impl Display for ClientError {
@@ -95,6 +455,21 @@ impl Display for ClientError {
             ClientError::RateLimited(rate_limit) => {
                 write!(f, "request was denied due to rate limit in effect until {:?}", rate_limit)
             }
+            ClientError::TooManyIds { given, max } => {
+                write!(f, "request was given {} ids, but at most {} are allowed", given, max)
+            }
+            ClientError::Unauthenticated => {
+                write!(f, "request requires an authenticated client")
+            }
+            ClientError::Rejected(status, message) => {
+                write!(f, "request was rejected with status code {}: {}", status, message)
+            }
+            ClientError::UnexpectedStatus(status) => {
+                write!(f, "server responded with unexpected status code {}", status)
+            }
+            ClientError::NotRecorded(method, uri) => {
+                write!(f, "no unplayed interaction for {} {} was recorded in the cassette", method, uri)
+            }
         }
     }
 }
@@ -0,0 +1,215 @@
+use crate::core::client::{Client, ClientError};
+use crate::lichess::external_engine::EngineWork;
+use crate::lichess::variant::Variant;
+
+use hyper::body::{Body, Bytes};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+use tokio::time::sleep_until;
+
+use std::error::Error;
+use std::ffi::OsStr;
+use std::io;
+use std::process::Stdio;
+
+/// The depth to which positions are analysed when Lichess does not request an
+/// infinite search and the engine has no default depth.
+pub const DEFAULT_DEPTH: u32 = 20;
+
+/// A local engine process speaking UCI over its standard input and output.
+/// Any executable which speaks enough UCI to answer `uci`, `isready`,
+/// `position` and `go` can be used, including a script standing in for a
+/// real engine.
+pub struct UciEngine {
+    // Held so that the process is killed when the engine is dropped.
+    _child: Child,
+    stdin: ChildStdin,
+    stdout: Lines<BufReader<ChildStdout>>,
+}
+
+impl UciEngine {
+    /// Spawn an engine process and wait for it to finish its UCI handshake.
+    pub async fn spawn<I>(program: impl AsRef<OsStr>, args: I) -> io::Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<OsStr>,
+    {
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()?;
+
+        let stdin = child.stdin.take().expect("engine stdin is piped");
+        let stdout = child.stdout.take().expect("engine stdout is piped");
+
+        let mut engine = UciEngine {
+            _child: child,
+            stdin,
+            stdout: BufReader::new(stdout).lines(),
+        };
+        engine.send("uci").await?;
+        engine.wait_for("uciok").await?;
+        Ok(engine)
+    }
+
+    /// Send a single command to the engine.
+    pub async fn send(&mut self, command: &str) -> io::Result<()> {
+        self.stdin.write_all(command.as_bytes()).await?;
+        self.stdin.write_all(b"\n").await?;
+        self.stdin.flush().await
+    }
+
+    /// Read a single line of output from the engine, returning `None` if the
+    /// engine has exited.
+    pub async fn read_line(&mut self) -> io::Result<Option<String>> {
+        self.stdout.next_line().await
+    }
+
+    /// Wait until the engine is ready to receive further commands.
+    pub async fn is_ready(&mut self) -> io::Result<()> {
+        self.send("isready").await?;
+        self.wait_for("readyok").await
+    }
+
+    /// Discard output from the engine until a line starting with the given
+    /// token is read.
+    async fn wait_for(&mut self, token: &str) -> io::Result<()> {
+        loop {
+            match self.read_line().await? {
+                Some(line) if line.starts_with(token) => return Ok(()),
+                Some(_) => continue,
+                None => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        format!("engine exited while waiting for {}", token),
+                    ))
+                }
+            }
+        }
+    }
+}
+
+/// Provides analysis for external engines registered with Lichess by
+/// acquiring work with the engines' provider secret, analysing it with a local
+/// [`UciEngine`], and streaming the engine's output back to Lichess.
+pub struct EngineProvider<'a> {
+    client: &'a Client,
+    provider_secret: String,
+    engine: UciEngine,
+    session_id: Option<String>,
+    // The variant the engine was last set to play. Engines start out playing
+    // standard chess, and keep the variant across units of work.
+    variant: Variant,
+}
+
+impl<'a> EngineProvider<'a> {
+    pub fn new(client: &'a Client, provider_secret: &str, engine: UciEngine) -> Self {
+        EngineProvider {
+            client,
+            provider_secret: provider_secret.to_string(),
+            engine,
+            session_id: None,
+            variant: Variant::Standard,
+        }
+    }
+
+    /// Acquire and analyse work forever. If the client is rate limited,
+    /// acquiring work is suspended until the rate limit is lifted. Returns
+    /// only if an error other than a rate limit occurs.
+    pub async fn run(&mut self) -> Result<(), Box<dyn Error>> {
+        loop {
+            match self.client.acquire_engine_work(&self.provider_secret).await {
+                Ok(Some(work)) => self.analyse(&work).await?,
+                Ok(None) => continue,
+                Err(e) => match e.downcast_ref::<ClientError>() {
+                    Some(ClientError::RateLimited(until)) => {
+                        sleep_until((*until).into()).await;
+                    }
+                    _ => return Err(e),
+                },
+            }
+        }
+    }
+
+    /// Analyse a single unit of work, streaming the engine's output to Lichess
+    /// until the engine finds a best move, or until Lichess stops listening.
+    pub async fn analyse(&mut self, work: &EngineWork) -> Result<(), Box<dyn Error>> {
+        let request = &work.work;
+        let engine = &mut self.engine;
+
+        if self.session_id.as_deref() != Some(request.session_id.as_str()) {
+            engine.send("ucinewgame").await?;
+            self.session_id = Some(request.session_id.clone());
+        }
+        if request.variant.uci() != self.variant.uci() {
+            engine
+                .send(&format!("setoption name UCI_Variant value {}", request.variant.uci()))
+                .await?;
+            self.variant = request.variant;
+        }
+        engine
+            .send(&format!("setoption name Threads value {}", request.threads))
+            .await?;
+        engine
+            .send(&format!("setoption name Hash value {}", request.hash))
+            .await?;
+        engine
+            .send(&format!("setoption name MultiPV value {}", request.multi_pv))
+            .await?;
+        engine.is_ready().await?;
+
+        let mut position = format!("position fen {}", request.initial_fen);
+        if !request.moves.is_empty() {
+            position.push_str(" moves ");
+            position.push_str(&request.moves.join(" "));
+        }
+        engine.send(&position).await?;
+
+        if request.infinite {
+            engine.send("go infinite").await?;
+        } else {
+            let depth = work.engine.default_depth.unwrap_or(DEFAULT_DEPTH);
+            engine.send(&format!("go depth {}", depth)).await?;
+        }
+
+        let (mut sender, body) = Body::channel();
+
+        let forward = async move {
+            let mut listening = true;
+            while let Some(line) = engine.read_line().await? {
+                let done = line.starts_with("bestmove");
+
+                if listening && (line.starts_with("info") || done) {
+                    let chunk = Bytes::from(format!("{}\n", line));
+                    if sender.send_data(chunk).await.is_err() {
+                        // Lichess has closed the connection, so the analysis
+                        // is no longer wanted. The engine still replies to
+                        // stop with a best move, which is discarded.
+                        listening = false;
+                        engine.send("stop").await?;
+                    }
+                }
+
+                if done {
+                    return Ok(listening);
+                }
+            }
+
+            Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "engine exited while analysing",
+            ))
+        };
+
+        let (submitted, forwarded) = tokio::join!(self.client.submit_engine_work(&work.id, body), forward);
+        match forwarded {
+            // If Lichess stopped listening, the submission may have ended in
+            // any number of ways, none of which are errors on our part.
+            Ok(false) => Ok(()),
+            Ok(true) => submitted,
+            Err(e) => Err(Box::new(e)),
+        }
+    }
+}
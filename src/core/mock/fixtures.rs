@@ -0,0 +1,16 @@
+//! Canned responses recorded from the Lichess API, with personal data
+//! replaced. They revolve around the users "georges" and "mary", and the
+//! games "yqfLYJ5E" and "3bFxPQbB" played between them.
+
+/// The real-time statuses of "georges", who is playing, and "mary", who is
+/// offline.
+pub const USER_STATUSES: &str = include_str!("../../../tests/fixtures/user_statuses.json");
+
+/// The users followed by the authenticated user, as an NDJSON stream.
+pub const FOLLOWING: &str = include_str!("../../../tests/fixtures/following.ndjson");
+
+/// Two exported [`Game`](crate::lichess::game::Game)s, as an NDJSON stream.
+pub const GAMES_NDJSON: &str = include_str!("../../../tests/fixtures/games.ndjson");
+
+/// The same two games, exported as PGN.
+pub const GAMES_PGN: &str = include_str!("../../../tests/fixtures/games.pgn");
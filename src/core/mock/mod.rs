@@ -0,0 +1,325 @@
+//! A local HTTP server which imitates the Lichess API, so that code built on
+//! this crate can be tested deterministically and offline. Routes are
+//! programmed with canned responses, and a [`Client`] obtained from the
+//! server sends every request to it instead of to Lichess.
+//!
+//! Only available with the `mock` feature.
+
+pub mod fixtures;
+
+use crate::core::client::Client;
+
+use futures::stream::{self, StreamExt};
+use hyper::body::Bytes;
+use hyper::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use tokio::sync::oneshot;
+
+use std::convert::Infallible;
+use std::error::Error;
+use std::io;
+use std::net::{SocketAddr, TcpListener};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// A canned response to be served by a [`MockServer`]. The body is sent as a
+/// sequence of chunks, each after a delay, so that streaming endpoints can
+/// be imitated.
+#[derive(Clone, Debug)]
+pub struct MockResponse {
+    status: StatusCode,
+    headers: Vec<(HeaderName, HeaderValue)>,
+    chunks: Vec<(Duration, Bytes)>,
+    abort: Option<Duration>,
+}
+
+impl MockResponse {
+    /// A response with the given status code and an empty body.
+    pub fn status(status: StatusCode) -> Self {
+        MockResponse {
+            status,
+            headers: Vec::new(),
+            chunks: Vec::new(),
+            abort: None,
+        }
+    }
+
+    /// A successful response with the given JSON body.
+    pub fn json(body: &str) -> Self {
+        MockResponse::status(StatusCode::OK)
+            .with_header(CONTENT_TYPE, "application/json")
+            .with_chunk(Duration::ZERO, body.to_string())
+    }
+
+    /// A successful response with the given plain text body.
+    pub fn text(body: &str) -> Self {
+        MockResponse::status(StatusCode::OK)
+            .with_header(CONTENT_TYPE, "text/plain")
+            .with_chunk(Duration::ZERO, body.to_string())
+    }
+
+    /// A successful response with the given PGN body.
+    pub fn pgn(body: &str) -> Self {
+        MockResponse::status(StatusCode::OK)
+            .with_header(CONTENT_TYPE, "application/x-chess-pgn")
+            .with_chunk(Duration::ZERO, body.to_string())
+    }
+
+    /// A successful NDJSON response streaming each non-empty line of the given
+    /// body as its own chunk, with the given delay before each.
+    pub fn ndjson(body: &str, interval: Duration) -> Self {
+        body.lines()
+            .filter(|line| !line.is_empty())
+            .fold(
+                MockResponse::status(StatusCode::OK).with_header(CONTENT_TYPE, "application/x-ndjson"),
+                |response, line| response.with_chunk(interval, format!("{}\n", line)),
+            )
+    }
+
+    /// A response with the given status code and a JSON body carrying the
+    /// given error message, as Lichess sends when it rejects a request.
+    pub fn error(status: StatusCode, message: &str) -> Self {
+        let body = serde_json::json!({ "error": message }).to_string();
+        MockResponse::status(status)
+            .with_header(CONTENT_TYPE, "application/json")
+            .with_chunk(Duration::ZERO, body.to_string())
+    }
+
+    /// A response with status code 429, as Lichess sends when a client
+    /// exceeds its rate limit.
+    pub fn rate_limited() -> Self {
+        MockResponse::status(StatusCode::TOO_MANY_REQUESTS)
+    }
+
+    /// Add a header to the response.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is not a valid header value.
+    pub fn with_header(mut self, name: HeaderName, value: &str) -> Self {
+        self.headers.push((name, HeaderValue::from_str(value).unwrap()));
+        self
+    }
+
+    /// Append a chunk to the body, sent after the given delay.
+    pub fn with_chunk(mut self, delay: Duration, chunk: impl Into<Bytes>) -> Self {
+        self.chunks.push((delay, chunk.into()));
+        self
+    }
+
+    /// Cut the body short after its chunks and the given delay, as when the
+    /// connection drops in the middle of a response.
+    pub fn with_abort(mut self, delay: Duration) -> Self {
+        self.abort = Some(delay);
+        self
+    }
+
+    fn into_response(self) -> Response<Body> {
+        let chunks = stream::iter(self.chunks).then(|(delay, chunk)| async move {
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+            Ok(chunk)
+        });
+        let abort = stream::iter(self.abort).then(|delay| async move {
+            tokio::time::sleep(delay).await;
+            Err(io::Error::new(io::ErrorKind::ConnectionAborted, "response aborted by the mock server"))
+        });
+        let body = Body::wrap_stream(chunks.chain(abort));
+
+        let mut response = Response::new(body);
+        *response.status_mut() = self.status;
+        for (name, value) in self.headers {
+            response.headers_mut().append(name, value);
+        }
+        response
+    }
+}
+
+/// A request received by a [`MockServer`].
+#[derive(Clone, Debug)]
+pub struct MockRequest {
+    pub method: Method,
+    pub path: String,
+    pub query: Option<String>,
+    pub headers: HeaderMap,
+    pub body: Bytes,
+}
+
+struct Route {
+    method: Method,
+    path: String,
+    response: MockResponse,
+    // The number of requests the route will still serve, or None if it
+    // serves any number.
+    remaining: Option<usize>,
+}
+
+#[derive(Default)]
+struct State {
+    routes: Vec<Route>,
+    requests: Vec<MockRequest>,
+}
+
+impl State {
+    /// Find the response to a request. Routes added later take precedence, so
+    /// that defaults can be overridden.
+    fn respond(&mut self, method: &Method, path: &str) -> Response<Body> {
+        let route = self
+            .routes
+            .iter_mut()
+            .rev()
+            .find(|route| route.method == method && route.path == path && route.remaining != Some(0));
+
+        match route {
+            Some(route) => {
+                if let Some(remaining) = &mut route.remaining {
+                    *remaining -= 1;
+                }
+                route.response.clone().into_response()
+            }
+            None => MockResponse::error(StatusCode::NOT_FOUND, "Not found").into_response(),
+        }
+    }
+}
+
+/// A local HTTP server which serves programmed routes. Requests to any other
+/// route are answered with status code 404. The server shuts down when
+/// dropped.
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl MockServer {
+    /// Start a server with no routes on a free local port. Must be called
+    /// from within a Tokio runtime.
+    pub async fn start() -> Result<Self, Box<dyn Error>> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        listener.set_nonblocking(true)?;
+        let addr = listener.local_addr()?;
+
+        let state = Arc::new(Mutex::new(State::default()));
+        let service_state = state.clone();
+        let make_service = make_service_fn(move |_| {
+            let state = service_state.clone();
+            async move { Ok::<_, Infallible>(service_fn(move |req| handle(state.clone(), req))) }
+        });
+
+        let (shutdown, shutdown_rx) = oneshot::channel::<()>();
+        let server = Server::from_tcp(listener)?
+            .serve(make_service)
+            .with_graceful_shutdown(async {
+                shutdown_rx.await.ok();
+            });
+        tokio::spawn(server);
+
+        Ok(MockServer {
+            addr,
+            state,
+            shutdown: Some(shutdown),
+        })
+    }
+
+    /// Start a server with routes serving the canned [`fixtures`]:
+    ///
+    /// - `GET /api/users/status` serves [`fixtures::USER_STATUSES`].
+    /// - `GET /api/rel/following` streams [`fixtures::FOLLOWING`].
+    /// - `GET /api/games/user/georges` streams [`fixtures::GAMES_NDJSON`].
+    /// - `GET /api/games/export/imports` serves [`fixtures::GAMES_PGN`].
+    pub async fn with_fixtures() -> Result<Self, Box<dyn Error>> {
+        let server = MockServer::start().await?;
+        server.route(Method::GET, "/api/users/status", MockResponse::json(fixtures::USER_STATUSES));
+        server.route(
+            Method::GET,
+            "/api/rel/following",
+            MockResponse::ndjson(fixtures::FOLLOWING, Duration::ZERO),
+        );
+        server.route(
+            Method::GET,
+            "/api/games/user/georges",
+            MockResponse::ndjson(fixtures::GAMES_NDJSON, Duration::ZERO),
+        );
+        server.route(Method::GET, "/api/games/export/imports", MockResponse::pgn(fixtures::GAMES_PGN));
+        Ok(server)
+    }
+
+    /// The base URL of the server, such as "http://127.0.0.1:41234".
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// A client which sends every request, including opening explorer,
+    /// tablebase and external engine requests, to the server.
+    pub fn client(&self) -> Client {
+        self.target(Client::new())
+    }
+
+    /// An authenticated client which sends every request to the server.
+    pub fn client_with_token(&self, token: &str) -> Client {
+        self.target(Client::with_token(token))
+    }
+
+    fn target(&self, mut client: Client) -> Client {
+        let url = self.url();
+        client.set_lichess_host(&url);
+        client.set_explorer_host(&url);
+        client.set_tablebase_host(&url);
+        client.set_engine_host(&url);
+        client
+    }
+
+    /// Serve the given response to every request with the given method and
+    /// path, ignoring the query string. Replaces any earlier route for the
+    /// same method and path.
+    pub fn route(&self, method: Method, path: &str, response: MockResponse) {
+        self.add_route(method, path, response, None);
+    }
+
+    /// Serve the given response to the next request with the given method and
+    /// path only, after which earlier routes apply again. This can be used to
+    /// imitate a rate limit which is lifted.
+    pub fn route_once(&self, method: Method, path: &str, response: MockResponse) {
+        self.add_route(method, path, response, Some(1));
+    }
+
+    fn add_route(&self, method: Method, path: &str, response: MockResponse, remaining: Option<usize>) {
+        self.state.lock().unwrap().routes.push(Route {
+            method,
+            path: path.to_string(),
+            response,
+            remaining,
+        });
+    }
+
+    /// Every request received so far, in the order received.
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.state.lock().unwrap().requests.clone()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            shutdown.send(()).ok();
+        }
+    }
+}
+
+async fn handle(state: Arc<Mutex<State>>, req: Request<Body>) -> Result<Response<Body>, hyper::Error> {
+    let (parts, body) = req.into_parts();
+    let body = hyper::body::to_bytes(body).await?;
+
+    let mut state = state.lock().unwrap();
+    let response = state.respond(&parts.method, parts.uri.path());
+    state.requests.push(MockRequest {
+        method: parts.method,
+        path: parts.uri.path().to_string(),
+        query: parts.uri.query().map(String::from),
+        headers: parts.headers,
+        body,
+    });
+    Ok(response)
+}
@@ -1,2 +1,11 @@
+pub mod cassette;
 pub mod client;
+pub mod engine_provider;
+#[cfg(feature = "mock")]
+pub mod mock;
 pub mod ndjson;
+pub mod pgn;
+pub mod presence;
+pub mod relay;
+
+pub mod api;
@@ -0,0 +1,202 @@
+use hyper::Body;
+use std::error::Error;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio_stream::Stream;
+
+/// The separator between games in a PGN stream. Lichess ends every game it
+/// streams with two blank lines.
+const SEPARATOR: &[u8] = b"\n\n\n";
+
+/// Split text containing any number of PGN games into the text of each game.
+/// A new game starts wherever a tag pair follows movetext, so games need not
+/// be separated by blank lines. Line endings are normalized to `\n`.
+pub fn split_games(pgn: &str) -> Vec<String> {
+    let mut games = Vec::new();
+    let mut current = String::new();
+    let mut in_movetext = false;
+    // Comments may span lines, and a line of a comment may itself start with
+    // a `[`, as in `[%clk 1:30:00]`.
+    let mut in_comment = false;
+
+    for line in pgn.lines() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with('[') && !in_comment {
+            if in_movetext {
+                games.push(current.trim().to_string());
+                current.clear();
+                in_movetext = false;
+            }
+        } else if !trimmed.is_empty() {
+            in_movetext = true;
+            for c in trimmed.chars() {
+                match c {
+                    '{' => in_comment = true,
+                    '}' => in_comment = false,
+                    _ => {}
+                }
+            }
+        }
+
+        current.push_str(line);
+        current.push('\n');
+    }
+
+    if !current.trim().is_empty() {
+        games.push(current.trim().to_string());
+    }
+    games
+}
+
+/// Get the value of a tag pair of a PGN game, if the game has the tag.
+pub fn tag<'a>(game: &'a str, name: &str) -> Option<&'a str> {
+    game.lines()
+        .map(str::trim)
+        .take_while(|line| line.is_empty() || line.starts_with('['))
+        .filter_map(|line| line.strip_prefix('[')?.strip_suffix(']'))
+        .find_map(|pair| {
+            let (tag_name, value) = pair.split_once(' ')?;
+            if tag_name == name {
+                value.trim().strip_prefix('"')?.strip_suffix('"')
+            } else {
+                None
+            }
+        })
+}
+
+/// A stream of PGN games, each yielded as the full text of the game.
+pub struct PgnStream {
+    buf: Vec<u8>,
+    waiting: bool,
+    body: Body,
+}
+
+impl PgnStream {
+    pub fn new(body: Body) -> Self {
+        PgnStream {
+            buf: Vec::new(),
+            waiting: false,
+            body,
+        }
+    }
+
+    /// Split the first complete game off the front of the buffer, if the
+    /// buffer contains one.
+    fn split_game(&mut self) -> Option<Vec<u8>> {
+        let i = memchr::memmem::find(&self.buf, SEPARATOR)?;
+        let rest = self.buf.split_off(i + SEPARATOR.len());
+        Some(std::mem::replace(&mut self.buf, rest))
+    }
+}
+
+impl Stream for PgnStream {
+    type Item = Result<String, Box<dyn Error>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if !self.waiting {
+                match self.split_game() {
+                    Some(game) => {
+                        let game = String::from_utf8(game)?;
+                        // Lichess sometimes pads the stream with extra blank
+                        // lines, which would otherwise yield empty games.
+                        if game.trim().is_empty() {
+                            continue;
+                        }
+                        return Poll::Ready(Some(Ok(game.trim().to_string())));
+                    }
+                    None => {
+                        self.waiting = true;
+                    }
+                }
+            }
+
+            match Pin::new(&mut self.body).poll_next(cx)? {
+                Poll::Ready(Some(buf)) => {
+                    self.buf.extend_from_slice(&buf);
+                    self.waiting = false;
+                }
+                Poll::Ready(None) => {
+                    // The body has ended, so whatever is left in the buffer is
+                    // the last game, even if it was not followed by the
+                    // separator.
+                    let game = String::from_utf8(std::mem::take(&mut self.buf))?;
+                    if game.trim().is_empty() {
+                        return Poll::Ready(None);
+                    }
+                    return Poll::Ready(Some(Ok(game.trim().to_string())));
+                }
+                Poll::Pending => {
+                    return Poll::Pending;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GAME: &str = "[White \"A\"]\n[Black \"B\"]\n\n1. e4 e5 *";
+
+    #[test]
+    fn split() {
+        let pgn = format!("{}\n\n\n{}\n", GAME, GAME.replace('A', "C"));
+        assert_eq!(split_games(&pgn), vec![GAME.to_string(), GAME.replace('A', "C")]);
+    }
+
+    #[test]
+    fn split_without_blank_lines() {
+        let pgn = format!("{}\n{}", GAME, GAME);
+        assert_eq!(split_games(&pgn), vec![GAME, GAME]);
+    }
+
+    #[test]
+    fn split_crlf() {
+        let pgn = format!("{}\r\n\r\n{}\r\n", GAME, GAME).replace("\n[", "\r\n[");
+        assert_eq!(split_games(&pgn), vec![GAME, GAME]);
+    }
+
+    #[test]
+    fn split_blank_lines_in_movetext() {
+        let game = "[White \"A\"]\n\n1. e4 e5\n\n2. Nf3 Nc6\n\n\n3. Bb5 *";
+        let pgn = format!("{}\n\n{}", game, GAME);
+        assert_eq!(split_games(&pgn), vec![game, GAME]);
+    }
+
+    #[test]
+    fn split_comment_lines() {
+        let game = "[White \"A\"]\n\n1. e4 { good move\n[%clk 1:29:58] } e5 *";
+        let pgn = format!("{}\n\n{}", game, GAME);
+        assert_eq!(split_games(&pgn), vec![game, GAME]);
+    }
+
+    #[test]
+    fn split_empty() {
+        assert!(split_games("").is_empty());
+        assert!(split_games("\r\n\n  \n").is_empty());
+    }
+
+    #[test]
+    fn tags() {
+        assert_eq!(tag(GAME, "White"), Some("A"));
+        assert_eq!(tag(GAME, "Black"), Some("B"));
+        assert_eq!(tag(GAME, "Round"), None);
+        assert_eq!(tag("1. e4 e5 *", "White"), None);
+    }
+
+    #[test]
+    fn tags_crlf() {
+        let game = "[White \"A\"]\r\n[Black \"B\"]\r\n\r\n1. e4 *";
+        assert_eq!(tag(game, "White"), Some("A"));
+        assert_eq!(tag(game, "Black"), Some("B"));
+    }
+
+    #[test]
+    fn tags_only_in_header() {
+        let game = "[White \"A\"]\n\n1. e4 {\n[Black \"B\"] } *";
+        assert_eq!(tag(game, "Black"), None);
+    }
+}
@@ -0,0 +1,134 @@
+use crate::core::api::users::MAX_STATUS_IDS;
+use crate::core::client::{Client, ClientError};
+use crate::lichess::status::UserStatus;
+
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::time::Duration;
+
+use futures::stream::{self, Stream};
+use tokio::time::{sleep, sleep_until};
+
+/// The shortest interval at which a [`PresenceWatcher`] will poll. Polling any
+/// faster than this risks tripping the Lichess rate limit.
+pub const MIN_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The pause between the requests of a single poll when more users are
+/// tracked than fit in one request, so that they are not sent back to back.
+pub const BATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A change in the presence of a tracked user.
+#[derive(Clone, Debug)]
+pub enum PresenceEvent {
+    WentOnline(UserStatus),
+    StartedPlaying(UserStatus),
+    WentOffline(UserStatus),
+}
+
+/// Tracks the presence of any number of users by periodically polling the
+/// user status endpoint, batching the tracked users into as few requests as
+/// possible. Each request covers at most 100 users, so a poll of more users
+/// makes several requests, spaced by [`BATCH_INTERVAL`].
+pub struct PresenceWatcher<'a> {
+    client: &'a Client,
+    interval: Duration,
+    tracked: Vec<String>,
+    last: HashMap<String, UserStatus>,
+}
+
+impl<'a> PresenceWatcher<'a> {
+    /// Create a new watcher which polls at the given interval. Intervals
+    /// shorter than [`MIN_POLL_INTERVAL`] are raised to it.
+    pub fn new(client: &'a Client, interval: Duration) -> Self {
+        PresenceWatcher {
+            client,
+            interval: interval.max(MIN_POLL_INTERVAL),
+            tracked: Vec::new(),
+            last: HashMap::new(),
+        }
+    }
+
+    /// Start tracking a user. Usernames are case-insensitive.
+    pub fn track(&mut self, username: &str) {
+        let id = username.to_lowercase();
+        if !self.tracked.contains(&id) {
+            self.tracked.push(id);
+        }
+    }
+
+    /// Stop tracking a user.
+    pub fn untrack(&mut self, username: &str) {
+        let id = username.to_lowercase();
+        self.tracked.retain(|tracked| tracked != &id);
+        self.last.remove(&id);
+    }
+
+    /// Poll the status of every tracked user once, and return the changes
+    /// since the previous poll. On the first poll every user is assumed to
+    /// have previously been offline, so users who are already online are
+    /// reported as having gone online. Users who are offline on the first
+    /// poll produce no event.
+    pub async fn poll(&mut self) -> Result<Vec<PresenceEvent>, Box<dyn Error>> {
+        let mut events = Vec::new();
+
+        for (i, chunk) in self.tracked.chunks(MAX_STATUS_IDS).enumerate() {
+            if i > 0 {
+                sleep(BATCH_INTERVAL).await;
+            }
+            let ids: Vec<&str> = chunk.iter().map(String::as_str).collect();
+            let statuses = self.client.get_user_statuses(&ids, false, true).await?;
+
+            for status in statuses {
+                let (was_online, was_playing) = match self.last.get(&status.id) {
+                    Some(last) => (last.online, last.playing),
+                    None => (false, false),
+                };
+
+                if status.online && !was_online {
+                    events.push(PresenceEvent::WentOnline(status.clone()));
+                }
+                if status.playing && !was_playing {
+                    events.push(PresenceEvent::StartedPlaying(status.clone()));
+                }
+                if !status.online && was_online {
+                    events.push(PresenceEvent::WentOffline(status.clone()));
+                }
+
+                self.last.insert(status.id.clone(), status);
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// Turn the watcher into an endless stream of presence events. If the
+    /// client is rate limited, polling is suspended until the rate limit is
+    /// lifted rather than yielding an error.
+    pub fn into_stream(self) -> impl Stream<Item = Result<PresenceEvent, Box<dyn Error>>> + 'a {
+        stream::unfold(
+            (self, VecDeque::new(), true),
+            |(mut watcher, mut queue, mut first)| async move {
+                loop {
+                    if let Some(event) = queue.pop_front() {
+                        return Some((Ok(event), (watcher, queue, first)));
+                    }
+
+                    if !first {
+                        sleep(watcher.interval).await;
+                    }
+                    first = false;
+
+                    match watcher.poll().await {
+                        Ok(events) => queue.extend(events),
+                        Err(e) => match e.downcast_ref::<ClientError>() {
+                            Some(ClientError::RateLimited(until)) => {
+                                sleep_until((*until).into()).await;
+                            }
+                            _ => return Some((Err(e), (watcher, queue, first))),
+                        },
+                    }
+                }
+            },
+        )
+    }
+}
@@ -0,0 +1,235 @@
+use crate::core::client::{Client, ClientError};
+use crate::core::pgn;
+use crate::lichess::broadcast::BroadcastPushResult;
+
+use tokio::time::{sleep, sleep_until, Instant};
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Relays the games in a local PGN file, such as the one written by DGT board
+/// software, to a broadcast round. The file is polled for changes, and
+/// whenever any game changed since it was last pushed, the whole file is
+/// pushed again, as Lichess matches pushed games to the games in the round by
+/// their tags and their position in the push.
+pub struct RelayDaemon<'a> {
+    client: &'a Client,
+    round_id: String,
+    path: PathBuf,
+    poll_interval: Duration,
+    push_interval: Duration,
+    max_retries: u32,
+    last_read: Option<String>,
+    last_push: Option<Instant>,
+    // Keyed by the tags identifying each game, with the text of the game as it
+    // was last accepted by Lichess. Only used to decide whether a push is
+    // needed.
+    pushed: HashMap<String, String>,
+}
+
+impl<'a> RelayDaemon<'a> {
+    /// Create a daemon relaying the games in the file at the given path to the
+    /// given broadcast round. By default the file is polled every second,
+    /// pushes are made at most every two seconds, and failed pushes are
+    /// retried three times.
+    pub fn new<P: AsRef<Path>>(client: &'a Client, round_id: &str, path: P) -> Self {
+        RelayDaemon {
+            client,
+            round_id: round_id.to_string(),
+            path: path.as_ref().to_path_buf(),
+            poll_interval: Duration::from_secs(1),
+            push_interval: Duration::from_secs(2),
+            max_retries: 3,
+            last_read: None,
+            last_push: None,
+            pushed: HashMap::new(),
+        }
+    }
+
+    /// Set the interval at which the file is checked for changes.
+    pub fn set_poll_interval(&mut self, interval: Duration) {
+        self.poll_interval = interval;
+    }
+
+    /// Set the minimum interval between consecutive pushes. Changes made to
+    /// the file in the meantime are pushed together.
+    pub fn set_push_interval(&mut self, interval: Duration) {
+        self.push_interval = interval;
+    }
+
+    /// Set the number of times a failed push is retried before giving up.
+    pub fn set_max_retries(&mut self, max_retries: u32) {
+        self.max_retries = max_retries;
+    }
+
+    /// Forget which games have been pushed, so that every game is pushed again
+    /// on the next sync.
+    pub fn reset(&mut self) {
+        self.last_read = None;
+        self.pushed.clear();
+    }
+
+    /// Read the file once, and push it if any game changed since it was last
+    /// pushed. Returns the result of the push, or `None` if nothing needed
+    /// pushing.
+    pub async fn sync(&mut self) -> Result<Option<BroadcastPushResult>, Box<dyn Error>> {
+        let contents = tokio::fs::read_to_string(&self.path).await?;
+        self.sync_contents(contents).await
+    }
+
+    /// Sync the file with the broadcast round forever. The file may be caught
+    /// while it is being rewritten, so a failure to read it is skipped until
+    /// the next poll. Returns only if a push fails after exhausting its
+    /// retries, or fails in a way retrying cannot fix.
+    pub async fn run(&mut self) -> Result<(), Box<dyn Error>> {
+        loop {
+            if let Ok(contents) = tokio::fs::read_to_string(&self.path).await {
+                self.sync_contents(contents).await?;
+            }
+            sleep(self.poll_interval).await;
+        }
+    }
+
+    async fn sync_contents(&mut self, contents: String) -> Result<Option<BroadcastPushResult>, Box<dyn Error>> {
+        if self.last_read.as_deref() == Some(contents.as_str()) {
+            return Ok(None);
+        }
+
+        let games: Vec<(String, String)> = pgn::split_games(&contents)
+            .into_iter()
+            .enumerate()
+            .map(|(i, game)| (game_key(i, &game), game))
+            .collect();
+
+        if games.iter().all(|(key, game)| self.pushed.get(key) == Some(game)) {
+            self.last_read = Some(contents);
+            return Ok(None);
+        }
+
+        let result = self.push(&contents).await?;
+
+        // Lichess reports on the pushed games in order. Games it rejected are
+        // left out, so that the file is pushed again on the next sync even if
+        // it has not changed.
+        let mut rejected = games.len() > result.games.len();
+        for ((key, game), pushed) in games.into_iter().zip(&result.games) {
+            if pushed.error.is_none() {
+                self.pushed.insert(key, game);
+            } else {
+                self.pushed.remove(&key);
+                rejected = true;
+            }
+        }
+        self.last_read = if rejected { None } else { Some(contents) };
+        Ok(Some(result))
+    }
+
+    /// Push PGN to the broadcast round, respecting the push interval and any
+    /// rate limit, and retrying failed pushes with exponential backoff.
+    async fn push(&mut self, pgn: &str) -> Result<BroadcastPushResult, Box<dyn Error>> {
+        let mut retries = 0;
+        let mut backoff = Duration::from_secs(1);
+
+        loop {
+            if let Some(last_push) = self.last_push {
+                sleep_until(last_push + self.push_interval).await;
+            }
+            self.last_push = Some(Instant::now());
+
+            match self.client.push_broadcast_pgn(&self.round_id, pgn).await {
+                Ok(result) => return Ok(result),
+                Err(e) => {
+                    if let Some(ClientError::RateLimited(until)) = e.downcast_ref::<ClientError>() {
+                        sleep_until((*until).into()).await;
+                        continue;
+                    }
+                    if retries >= self.max_retries || !is_retryable(e.as_ref()) {
+                        return Err(e);
+                    }
+                    retries += 1;
+                    sleep(backoff).await;
+                    backoff *= 2;
+                }
+            }
+        }
+    }
+}
+
+/// Whether a failed push might succeed if retried. Pushes which Lichess
+/// rejected, or which failed for lack of authentication or an unknown round,
+/// will fail the same way again.
+fn is_retryable(e: &(dyn Error + 'static)) -> bool {
+    match e.downcast_ref::<ClientError>() {
+        Some(ClientError::Rejected(..)) | Some(ClientError::Unauthenticated) => false,
+        Some(ClientError::UnexpectedStatus(status)) => !status.is_client_error(),
+        _ => true,
+    }
+}
+
+/// Identify a game by the tags which Lichess uses to match pushed games to
+/// the games already in a round, falling back to its position in the file.
+fn game_key(index: usize, game: &str) -> String {
+    let tags: Vec<String> = ["White", "Black", "Round", "Board"]
+        .iter()
+        .filter_map(|name| Some(format!("{}={}", name, pgn::tag(game, name)?)))
+        .collect();
+
+    if tags.is_empty() {
+        format!("#{}", index)
+    } else {
+        tags.join("\u{0}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_from_tags() {
+        let game = "[White \"Carlsen, M.\"]\n[Black \"Caruana, F.\"]\n[Round \"3.1\"]\n\n1. e4 *";
+        assert_eq!(game_key(7, game), "White=Carlsen, M.\u{0}Black=Caruana, F.\u{0}Round=3.1");
+    }
+
+    #[test]
+    fn key_without_tags() {
+        assert_eq!(game_key(0, "1. e4 e5 *"), "#0");
+        assert_ne!(game_key(0, "1. e4 e5 *"), game_key(1, "1. e4 e5 *"));
+    }
+
+    #[test]
+    fn key_with_missing_tags() {
+        // The names of the tags are part of the key, so that games missing
+        // different tags cannot be mistaken for each other.
+        let white_and_round = "[White \"A\"]\n[Round \"1\"]\n\n*";
+        let white_and_black = "[White \"A\"]\n[Black \"1\"]\n\n*";
+        assert_ne!(game_key(0, white_and_round), game_key(0, white_and_black));
+    }
+
+    #[test]
+    fn key_across_rounds_and_boards() {
+        let game = |round: &str, board: &str| {
+            format!("[White \"A\"]\n[Black \"B\"]\n[Round \"{}\"]\n[Board \"{}\"]\n\n*", round, board)
+        };
+        // The same pairing in another round, such as in a double round robin,
+        // or on another board, is another game.
+        assert_ne!(game_key(0, &game("1", "1")), game_key(0, &game("2", "1")));
+        assert_ne!(game_key(0, &game("1", "1")), game_key(0, &game("1", "2")));
+        assert_eq!(game_key(0, &game("1", "1")), game_key(5, &game("1", "1")));
+    }
+
+    #[test]
+    fn retryable() {
+        use hyper::StatusCode;
+
+        let rejected = ClientError::Rejected(StatusCode::BAD_REQUEST, "Invalid PGN".to_string());
+        assert!(!is_retryable(&rejected));
+        assert!(!is_retryable(&ClientError::Unauthenticated));
+        assert!(!is_retryable(&ClientError::UnexpectedStatus(StatusCode::NOT_FOUND)));
+        assert!(is_retryable(&ClientError::UnexpectedStatus(StatusCode::BAD_GATEWAY)));
+        let io = std::io::Error::new(std::io::ErrorKind::ConnectionReset, "reset");
+        assert!(is_retryable(&io));
+    }
+}
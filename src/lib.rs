@@ -1,2 +1,3 @@
+pub mod analytics;
 pub mod core;
 pub mod lichess;
@@ -0,0 +1,290 @@
+use crate::lichess::game::Color;
+use crate::lichess::key::Key;
+use crate::lichess::perf_type::PerfType;
+use crate::lichess::speed::Speed;
+use crate::lichess::user::LightUser;
+
+use chrono::serde::ts_milliseconds;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use std::collections::HashMap;
+
+/// Representation of a single interval, usually a day, of a Lichess user's
+/// activity. Every kind of activity is optional, and is absent when the user
+/// did nothing of that kind during the interval.
+/// Derived from [lila.activity.ActivityView][1].
+/// [1]: <https://github.com/ornicar/lila/blob/master/modules/activity/src/main/ActivityView.scala>
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Activity {
+    pub interval: ActivityInterval,
+    #[serde(default)]
+    pub games: HashMap<ActivityPerf, ActivityScore>,
+    #[serde(default)]
+    pub puzzles: Option<ActivityPuzzles>,
+    #[serde(default)]
+    pub storm: Option<ActivityRuns>,
+    #[serde(default)]
+    pub racer: Option<ActivityRuns>,
+    #[serde(default)]
+    pub streak: Option<ActivityRuns>,
+    #[serde(default)]
+    pub tournaments: Option<ActivityTournaments>,
+    #[serde(default)]
+    pub practice: Vec<ActivityPractice>,
+    #[serde(default)]
+    pub simuls: Vec<ActivitySimul>,
+    #[serde(default)]
+    pub correspondence_moves: Option<ActivityCorrespondence>,
+    #[serde(default)]
+    pub correspondence_ends: Option<ActivityCorrespondenceEnds>,
+    #[serde(default)]
+    pub follows: Option<ActivityFollows>,
+    #[serde(default)]
+    pub studies: Vec<ActivityStudy>,
+    #[serde(default)]
+    pub teams: Vec<ActivityTeam>,
+    #[serde(default)]
+    pub posts: Vec<ActivityPosts>,
+    #[serde(default)]
+    pub patron: Option<ActivityPatron>,
+    #[serde(default)]
+    pub stream: bool,
+}
+
+/// The interval of time covered by an [`Activity`].
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ActivityInterval {
+    #[serde(with = "ts_milliseconds")]
+    pub start: DateTime<Utc>,
+    #[serde(with = "ts_milliseconds")]
+    pub end: DateTime<Utc>,
+}
+
+/// The perf in which the games of an [`Activity`] were played.
+pub type ActivityPerf = Key<PerfType>;
+
+/// Wins, losses and draws over an interval, with the rating progression over
+/// the same interval.
+/// Derived from [lila.activity.activities.Score][1].
+/// [1]: <https://github.com/ornicar/lila/blob/master/modules/activity/src/main/activities.scala>
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityScore {
+    pub win: i32,
+    pub loss: i32,
+    pub draw: i32,
+    pub rp: Option<RatingProgression>,
+}
+
+/// A rating before and after an interval.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RatingProgression {
+    pub before: i32,
+    pub after: i32,
+}
+
+impl RatingProgression {
+    /// The change in rating over the interval.
+    pub fn diff(&self) -> i32 {
+        self.after - self.before
+    }
+}
+
+/// Puzzles solved over an interval.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ActivityPuzzles {
+    pub score: ActivityScore,
+}
+
+/// Runs of Puzzle Storm, Puzzle Racer or Puzzle Streak over an interval, with
+/// the best score achieved.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ActivityRuns {
+    pub runs: i32,
+    pub score: i32,
+}
+
+/// Tournaments played over an interval.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ActivityTournaments {
+    pub nb: i32,
+    #[serde(default)]
+    pub best: Vec<ActivityTournament>,
+}
+
+/// A user's result in a single tournament.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityTournament {
+    pub tournament: ActivityTournamentInfo,
+    pub nb_games: i32,
+    pub score: i32,
+    pub rank: i32,
+    pub rank_percent: i32,
+}
+
+/// The tournament in which an [`ActivityTournament`] result was achieved.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ActivityTournamentInfo {
+    pub id: String,
+    pub name: String,
+}
+
+/// Positions completed in a practice study.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityPractice {
+    pub url: String,
+    pub name: String,
+    pub nb_positions: i32,
+}
+
+/// A simul hosted or played in.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivitySimul {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub is_host: bool,
+    #[serde(default)]
+    pub variants: Vec<String>,
+    #[serde(default)]
+    pub score: Option<ActivityScore>,
+}
+
+/// Moves played in correspondence games.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ActivityCorrespondence {
+    pub nb: i32,
+    #[serde(default)]
+    pub games: Vec<ActivityCorrespondenceGame>,
+}
+
+/// Correspondence games which ended.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ActivityCorrespondenceEnds {
+    pub score: ActivityScore,
+    #[serde(default)]
+    pub games: Vec<ActivityCorrespondenceGame>,
+}
+
+/// A correspondence game referenced by an [`Activity`].
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ActivityCorrespondenceGame {
+    pub id: String,
+    pub color: Color,
+    pub url: String,
+    pub speed: Speed,
+    pub perf: PerfType,
+    pub rated: bool,
+    pub opponent: ActivityOpponent,
+}
+
+/// The opponent in an [`ActivityCorrespondenceGame`].
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ActivityOpponent {
+    #[serde(deserialize_with = "deserialize_user")]
+    pub user: LightUser,
+    #[serde(default)]
+    pub rating: Option<i32>,
+}
+
+/// Users who started following, or were followed by, a user.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ActivityFollows {
+    #[serde(default, rename = "in")]
+    pub followers: Option<ActivityFollowList>,
+    #[serde(default, rename = "out")]
+    pub following: Option<ActivityFollowList>,
+}
+
+/// A list of users, possibly truncated, with the total number of users.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ActivityFollowList {
+    // Lichess only sends the ids of these users, which are also used as their
+    // names.
+    #[serde(rename = "ids", deserialize_with = "deserialize_users")]
+    pub users: Vec<LightUser>,
+    #[serde(default)]
+    pub nb: Option<i32>,
+}
+
+/// A study created.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ActivityStudy {
+    pub id: String,
+    pub name: String,
+}
+
+/// A team joined.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ActivityTeam {
+    pub url: String,
+    pub name: String,
+}
+
+/// Posts made in a single forum topic.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityPosts {
+    pub topic_url: String,
+    pub topic_name: String,
+    pub posts: Vec<ActivityPost>,
+}
+
+/// A single forum post.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ActivityPost {
+    pub url: String,
+    pub text: String,
+}
+
+/// Months of Patron support.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ActivityPatron {
+    pub months: i32,
+}
+
+/// A user in an [`Activity`], which Lichess usually identifies by name or id
+/// alone, but which is accepted as a full [`LightUser`] too so that
+/// serialized activities can be read back.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ActivityUser {
+    Name(String),
+    User(LightUser),
+}
+
+impl From<ActivityUser> for LightUser {
+    fn from(user: ActivityUser) -> Self {
+        match user {
+            ActivityUser::Name(name) => LightUser {
+                id: name.to_lowercase(),
+                name,
+                title: None,
+                patron: false,
+            },
+            ActivityUser::User(user) => user,
+        }
+    }
+}
+
+fn deserialize_user<'de, D>(deserializer: D) -> Result<LightUser, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(ActivityUser::deserialize(deserializer)?.into())
+}
+
+fn deserialize_users<'de, D>(deserializer: D) -> Result<Vec<LightUser>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Vec::<ActivityUser>::deserialize(deserializer)?
+        .into_iter()
+        .map(LightUser::from)
+        .collect())
+}
@@ -0,0 +1,80 @@
+use chrono::serde::{ts_milliseconds, ts_milliseconds_option};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use std::collections::HashMap;
+
+/// A broadcast tournament together with its rounds.
+/// Derived from [lila.relay.JsonView][1].
+/// [1]: <https://github.com/ornicar/lila/blob/master/modules/relay/src/main/JsonView.scala>
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Broadcast {
+    pub tour: BroadcastTour,
+    #[serde(default)]
+    pub rounds: Vec<BroadcastRound>,
+}
+
+/// A broadcast tournament, relaying games played over the board.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BroadcastTour {
+    pub id: String,
+    pub name: String,
+    pub slug: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(with = "ts_milliseconds")]
+    pub created_at: DateTime<Utc>,
+    // Only present for official broadcasts, with higher tiers being more
+    // prominently featured.
+    #[serde(default)]
+    pub tier: Option<i32>,
+    // The full description of the tournament, rendered as HTML.
+    #[serde(default)]
+    pub markup: Option<String>,
+    pub url: String,
+}
+
+/// A round of a broadcast tournament.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BroadcastRound {
+    pub id: String,
+    pub name: String,
+    pub slug: String,
+    #[serde(with = "ts_milliseconds")]
+    pub created_at: DateTime<Utc>,
+    #[serde(default, with = "ts_milliseconds_option")]
+    pub starts_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub finished: bool,
+    #[serde(default)]
+    pub ongoing: bool,
+    pub url: String,
+}
+
+/// A newly created or updated broadcast round, with the tournament it belongs
+/// to.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BroadcastRoundWithTour {
+    pub round: BroadcastRound,
+    pub tour: BroadcastTour,
+}
+
+/// The result of pushing PGN to a broadcast round, with one entry per game in
+/// the PGN.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BroadcastPushResult {
+    pub games: Vec<BroadcastPushedGame>,
+}
+
+/// The result of pushing a single game to a broadcast round.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BroadcastPushedGame {
+    pub tags: HashMap<String, String>,
+    // The number of moves in the game, or zero if it could not be parsed.
+    #[serde(default)]
+    pub moves: i32,
+    #[serde(default)]
+    pub error: Option<String>,
+}
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+/// A cached engine evaluation of a position from the Lichess cloud.
+/// Derived from [lila.evalCache.JsonView][1].
+/// [1]: <https://github.com/ornicar/lila/blob/master/modules/evalCache/src/main/JsonView.scala>
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CloudEval {
+    pub fen: String,
+    pub knodes: u64,
+    pub depth: u32,
+    // Sorted from best to worst for the side to move.
+    pub pvs: Vec<PrincipalVariation>,
+}
+
+impl CloudEval {
+    /// The best line for the side to move.
+    pub fn best(&self) -> Option<&PrincipalVariation> {
+        self.pvs.first()
+    }
+}
+
+/// A single line of a [`CloudEval`]. Exactly one of `cp` and `mate` is
+/// present, from the perspective of white.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PrincipalVariation {
+    // Space-separated moves in UCI notation.
+    pub moves: String,
+    #[serde(default)]
+    pub cp: Option<i32>,
+    #[serde(default)]
+    pub mate: Option<i32>,
+}
+
+impl PrincipalVariation {
+    /// The moves of the line in UCI notation.
+    pub fn uci(&self) -> impl Iterator<Item = &str> {
+        self.moves.split_whitespace()
+    }
+}
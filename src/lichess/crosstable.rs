@@ -0,0 +1,104 @@
+use crate::lichess::game::Game;
+use crate::lichess::key::Key;
+use crate::lichess::perf_type::PerfType;
+
+use serde::{Deserialize, Serialize};
+
+use std::collections::HashMap;
+
+/// The results of all games between two users.
+/// Derived from [lila.game.Crosstable][1].
+/// [1]: <https://github.com/ornicar/lila/blob/master/modules/game/src/main/Crosstable.scala>
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Crosstable {
+    // Keyed by user id. Draws count as half a point for each user.
+    pub users: HashMap<String, f64>,
+    pub nb_games: i32,
+    // Only present when requested, and only while the users are in the middle
+    // of a match, meaning they have played each other recently.
+    #[serde(default)]
+    pub matchup: Option<Matchup>,
+}
+
+/// The results of the games between two users in their current match.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Matchup {
+    pub users: HashMap<String, f64>,
+    pub nb_games: i32,
+}
+
+/// Head-to-head statistics of a user against an opponent, broken down by perf.
+/// Unlike a [`Crosstable`], which only reports total scores, this is computed
+/// from the games themselves.
+#[derive(Debug)]
+pub struct HeadToHead {
+    pub crosstable: Crosstable,
+    pub perfs: HashMap<Key<PerfType>, HeadToHeadRecord>,
+}
+
+impl HeadToHead {
+    /// Create head-to-head statistics with no games recorded.
+    pub fn new(crosstable: Crosstable) -> Self {
+        HeadToHead {
+            crosstable,
+            perfs: HashMap::new(),
+        }
+    }
+
+    /// Record the result of a game from the perspective of the given user.
+    /// Games the user did not play in, and games without a result, are
+    /// ignored.
+    pub fn record(&mut self, user_id: &str, game: &Game) {
+        if !game.is_finished() {
+            return;
+        }
+        let color = match game.color_of(user_id) {
+            Some(color) => color,
+            None => return,
+        };
+
+        let record = self.perfs.entry(game.perf.clone()).or_default();
+        match game.winner {
+            Some(winner) if winner == color => record.wins += 1,
+            Some(_) => record.losses += 1,
+            None => record.draws += 1,
+        }
+        if let Some(diff) = game.players.get(color).rating_diff {
+            record.rating_diff += diff;
+        }
+    }
+
+    /// The combined record across all perfs.
+    pub fn total(&self) -> HeadToHeadRecord {
+        self.perfs.values().fold(HeadToHeadRecord::default(), |acc, record| HeadToHeadRecord {
+            wins: acc.wins + record.wins,
+            losses: acc.losses + record.losses,
+            draws: acc.draws + record.draws,
+            rating_diff: acc.rating_diff + record.rating_diff,
+        })
+    }
+}
+
+/// A user's record against an opponent in a single perf.
+#[derive(Default, Clone, Copy, Debug)]
+pub struct HeadToHeadRecord {
+    pub wins: i32,
+    pub losses: i32,
+    pub draws: i32,
+    // The net rating gained from rated games against the opponent.
+    pub rating_diff: i32,
+}
+
+impl HeadToHeadRecord {
+    /// The number of games played.
+    pub fn games(&self) -> i32 {
+        self.wins + self.losses + self.draws
+    }
+
+    /// The score, counting draws as half a point.
+    pub fn score(&self) -> f64 {
+        self.wins as f64 + self.draws as f64 / 2.0
+    }
+}
@@ -0,0 +1,101 @@
+use crate::lichess::game::Color;
+use crate::lichess::speed::Speed;
+
+use serde::{Deserialize, Serialize};
+
+/// Statistics of the games reaching a position in one of the opening explorer
+/// databases.
+/// Derived from [lila-openingexplorer][1].
+/// [1]: <https://github.com/lichess-org/lila-openingexplorer/blob/master/src/api/response.rs>
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExplorerResult {
+    pub white: u64,
+    pub draws: u64,
+    pub black: u64,
+    pub moves: Vec<ExplorerMove>,
+    #[serde(default)]
+    pub top_games: Vec<ExplorerGame>,
+    #[serde(default)]
+    pub recent_games: Vec<ExplorerGame>,
+    #[serde(default)]
+    pub opening: Option<ExplorerOpening>,
+    // Only present in player database results while the player's games are
+    // still being indexed.
+    #[serde(default)]
+    pub queue_position: Option<u64>,
+}
+
+impl ExplorerResult {
+    /// The total number of games reaching the position.
+    pub fn total(&self) -> u64 {
+        self.white + self.draws + self.black
+    }
+}
+
+/// Statistics of the games in which a move was played from a position.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExplorerMove {
+    pub uci: String,
+    pub san: String,
+    pub white: u64,
+    pub draws: u64,
+    pub black: u64,
+    // Absent in player database results, which report the rating of the
+    // player's opponents instead.
+    #[serde(default)]
+    pub average_rating: Option<i32>,
+    #[serde(default)]
+    pub average_opponent_rating: Option<i32>,
+    #[serde(default)]
+    pub performance: Option<i32>,
+    // The only game in which the move was played, if it was played in exactly
+    // one game.
+    #[serde(default)]
+    pub game: Option<ExplorerGame>,
+    #[serde(default)]
+    pub opening: Option<ExplorerOpening>,
+}
+
+impl ExplorerMove {
+    /// The total number of games in which the move was played.
+    pub fn total(&self) -> u64 {
+        self.white + self.draws + self.black
+    }
+}
+
+/// A game in one of the opening explorer databases.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ExplorerGame {
+    pub id: String,
+    // The move played in the game from the position queried, only present in
+    // lists of top and recent games.
+    #[serde(default)]
+    pub uci: Option<String>,
+    #[serde(default)]
+    pub winner: Option<Color>,
+    pub white: ExplorerPlayer,
+    pub black: ExplorerPlayer,
+    pub year: i32,
+    #[serde(default)]
+    pub month: Option<String>,
+    #[serde(default)]
+    pub speed: Option<Speed>,
+    #[serde(default)]
+    pub mode: Option<String>,
+}
+
+/// A player in an [`ExplorerGame`].
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ExplorerPlayer {
+    pub name: String,
+    pub rating: i32,
+}
+
+/// The name of the opening of a position.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ExplorerOpening {
+    pub eco: String,
+    pub name: String,
+}
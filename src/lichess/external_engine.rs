@@ -0,0 +1,86 @@
+use crate::lichess::variant::Variant;
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// An external engine registered by a Lichess user.
+/// Derived from [lila.analyse.ExternalEngine][1].
+/// [1]: <https://github.com/ornicar/lila/blob/master/modules/analyse/src/main/ExternalEngine.scala>
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalEngine {
+    pub id: String,
+    pub name: String,
+    // Used by clients to request analysis from the engine.
+    pub client_secret: String,
+    pub user_id: String,
+    pub max_threads: u32,
+    pub max_hash: u32,
+    #[serde(default)]
+    pub default_depth: Option<u32>,
+    // UCI_Variant names, such as "chess" or "kingofthehill".
+    pub variants: Vec<String>,
+    #[serde(default)]
+    pub provider_data: Option<String>,
+}
+
+/// The settings used to register a new external engine, or to update an
+/// existing one.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalEngineRegistration {
+    pub name: String,
+    pub max_threads: u32,
+    pub max_hash: u32,
+    pub default_depth: u32,
+    pub variants: Vec<String>,
+    // A secret used by the provider to acquire analysis work for the engine.
+    // Should be random, and is never revealed to clients.
+    pub provider_secret: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider_data: Option<String>,
+}
+
+/// A unit of analysis work requested from an external engine, acquired by its
+/// provider.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct EngineWork {
+    pub id: String,
+    pub work: EngineWorkRequest,
+    pub engine: ExternalEngine,
+}
+
+/// The analysis requested by an [`EngineWork`].
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineWorkRequest {
+    // Consecutive work in the same session analyses related positions, so the
+    // engine does not need to be reset between them.
+    pub session_id: String,
+    pub threads: u32,
+    pub hash: u32,
+    #[serde(default)]
+    pub infinite: bool,
+    pub multi_pv: u32,
+    // Named as by UCI engines, so Chess960 is requested as standard.
+    #[serde(serialize_with = "serialize_uci_variant", deserialize_with = "deserialize_uci_variant")]
+    pub variant: Variant,
+    pub initial_fen: String,
+    // Moves in UCI notation played from the initial FEN.
+    pub moves: Vec<String>,
+}
+
+fn serialize_uci_variant<S>(variant: &Variant, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(variant.uci())
+}
+
+fn deserialize_uci_variant<'de, D>(deserializer: D) -> Result<Variant, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let name = String::deserialize(deserializer)?;
+    Variant::from_uci(&name).map_err(D::Error::custom)
+}
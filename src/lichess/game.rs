@@ -0,0 +1,219 @@
+use crate::lichess::key::Key;
+use crate::lichess::perf_type::PerfType;
+use crate::lichess::speed::Speed;
+use crate::lichess::user::LightUser;
+use crate::lichess::variant::Variant;
+
+use chrono::serde::ts_milliseconds;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use std::fmt::Display;
+
+/// Representation of a Lichess game, as exported in JSON.
+/// Derived from [lila.game.JsonView][1].
+/// [1]: <https://github.com/ornicar/lila/blob/master/modules/game/src/main/JsonView.scala>
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Game {
+    pub id: String,
+    pub rated: bool,
+    pub variant: Key<Variant>,
+    pub speed: Key<Speed>,
+    pub perf: Key<PerfType>,
+    #[serde(with = "ts_milliseconds")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "ts_milliseconds")]
+    pub last_move_at: DateTime<Utc>,
+    pub status: String,
+    pub players: GamePlayers,
+    #[serde(default)]
+    pub winner: Option<Color>,
+    #[serde(default)]
+    pub opening: Option<Opening>,
+    #[serde(default)]
+    pub moves: Option<String>,
+    #[serde(default)]
+    pub pgn: Option<String>,
+    #[serde(default)]
+    pub initial_fen: Option<String>,
+    #[serde(default)]
+    pub clock: Option<GameClock>,
+    #[serde(default)]
+    pub days_per_turn: Option<i32>,
+    #[serde(default)]
+    pub tournament: Option<String>,
+    #[serde(default)]
+    pub swiss: Option<String>,
+}
+
+impl Game {
+    /// Whether the game has ended with a result. Games which are still being
+    /// played, or which were aborted before they began, have no result.
+    pub fn is_finished(&self) -> bool {
+        !matches!(
+            self.status.as_str(),
+            "created" | "started" | "aborted" | "noStart" | "unknownFinish"
+        )
+    }
+
+    /// The color played by a user in the game, if they played in it.
+    pub fn color_of(&self, user_id: &str) -> Option<Color> {
+        let is = |player: &GamePlayer| {
+            player
+                .user
+                .as_ref()
+                .is_some_and(|user| user.id.eq_ignore_ascii_case(user_id))
+        };
+
+        if is(&self.players.white) {
+            Some(Color::White)
+        } else if is(&self.players.black) {
+            Some(Color::Black)
+        } else {
+            None
+        }
+    }
+}
+
+/// The players of a [`Game`].
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GamePlayers {
+    pub white: GamePlayer,
+    pub black: GamePlayer,
+}
+
+impl GamePlayers {
+    /// The player playing the given color.
+    pub fn get(&self, color: Color) -> &GamePlayer {
+        match color {
+            Color::White => &self.white,
+            Color::Black => &self.black,
+        }
+    }
+}
+
+/// A player in a [`Game`]. Exactly one of `user`, `ai_level` or `name` is
+/// present, depending on whether the player is a Lichess user, the Lichess AI,
+/// or a player named in an imported game.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GamePlayer {
+    #[serde(default)]
+    pub user: Option<LightUser>,
+    #[serde(default)]
+    pub rating: Option<i32>,
+    #[serde(default)]
+    pub rating_diff: Option<i32>,
+    #[serde(default)]
+    pub provisional: bool,
+    #[serde(default)]
+    pub berserk: bool,
+    #[serde(default)]
+    pub ai_level: Option<i32>,
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+/// The color of a side in a game.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Color {
+    White,
+    Black,
+}
+
+impl Color {
+    /// The other color.
+    pub fn opposite(self) -> Self {
+        match self {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        }
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Color::White => write!(f, "white"),
+            Color::Black => write!(f, "black"),
+        }
+    }
+}
+
+/// The opening of a [`Game`].
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Opening {
+    pub eco: String,
+    pub name: String,
+    pub ply: i32,
+}
+
+/// The clock settings of a [`Game`], in seconds.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GameClock {
+    pub initial: i32,
+    pub increment: i32,
+    pub total_time: i32,
+}
+
+/// A game created by importing PGN.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ImportedGame {
+    pub id: String,
+    pub url: String,
+}
+
+/// A game being played by the authenticated user.
+/// Derived from [lila.api.AccountApi][1].
+/// [1]: <https://github.com/ornicar/lila/blob/master/app/controllers/Account.scala>
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct OngoingGame {
+    pub game_id: String,
+    // The game id followed by the player id, needed to make moves in the game.
+    pub full_id: String,
+    pub color: Color,
+    pub fen: String,
+    pub has_moved: bool,
+    pub is_my_turn: bool,
+    // In UCI notation. Empty if no move has been played.
+    #[serde(default)]
+    pub last_move: String,
+    pub opponent: OngoingGameOpponent,
+    pub perf: Key<PerfType>,
+    pub rated: bool,
+    // Absent for games without a clock.
+    #[serde(default)]
+    pub seconds_left: Option<i32>,
+    pub source: String,
+    pub speed: Key<Speed>,
+    pub variant: OngoingGameVariant,
+    #[serde(default)]
+    pub tournament_id: Option<String>,
+    #[serde(default)]
+    pub swiss_id: Option<String>,
+}
+
+/// The opponent in an [`OngoingGame`]. The id is absent when the opponent is
+/// the Lichess AI, in which case its level is present instead.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct OngoingGameOpponent {
+    #[serde(default)]
+    pub id: Option<String>,
+    pub username: String,
+    #[serde(default)]
+    pub rating: Option<i32>,
+    #[serde(default)]
+    pub ai: Option<i32>,
+}
+
+/// The variant of an [`OngoingGame`].
+#[derive(Serialize, Deserialize, Debug)]
+pub struct OngoingGameVariant {
+    pub key: Key<Variant>,
+    pub name: String,
+}
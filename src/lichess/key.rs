@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+use std::error::Error;
+use std::fmt::Display;
+
+/// A key which Lichess sent for a [`PerfType`], [`Variant`] or [`Speed`].
+/// Keys unknown to this crate are kept as they were sent, so that perfs,
+/// variants and speeds added after this crate was released do not fail to
+/// deserialize, which would end a whole stream of games.
+///
+/// [`PerfType`]: crate::lichess::perf_type::PerfType
+/// [`Variant`]: crate::lichess::variant::Variant
+/// [`Speed`]: crate::lichess::speed::Speed
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash, Debug)]
+#[serde(untagged)]
+pub enum Key<T> {
+    Known(T),
+    Other(String),
+}
+
+impl<T> Key<T> {
+    /// The known perf, variant or speed, if any.
+    pub fn known(&self) -> Option<&T> {
+        match self {
+            Key::Known(known) => Some(known),
+            Key::Other(_) => None,
+        }
+    }
+}
+
+impl<T> From<T> for Key<T> {
+    fn from(known: T) -> Self {
+        Key::Known(known)
+    }
+}
+
+/// An error in parsing a [`PerfType`], [`Variant`] or [`Speed`] from a key
+/// which Lichess does not use.
+///
+/// [`PerfType`]: crate::lichess::perf_type::PerfType
+/// [`Variant`]: crate::lichess::variant::Variant
+/// [`Speed`]: crate::lichess::speed::Speed
+#[derive(Debug)]
+pub struct ParseKeyError(pub String);
+
+impl Display for ParseKeyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown key {:?}", self.0)
+    }
+}
+
+impl Error for ParseKeyError {}
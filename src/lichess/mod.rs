@@ -1,9 +1,25 @@
+pub mod activity;
+pub mod broadcast;
+pub mod cloud_eval;
 pub mod count;
+pub mod crosstable;
+pub mod explorer;
+pub mod external_engine;
+pub mod game;
+pub mod key;
+pub mod perf_stat;
+pub mod perf_type;
 pub mod performances;
 pub mod playtime;
 pub mod profile;
+pub mod simul;
+pub mod speed;
+pub mod status;
+pub mod study;
+pub mod tablebase;
 pub mod title;
 pub mod user;
+pub mod variant;
 
 pub mod top10s;
 
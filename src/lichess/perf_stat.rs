@@ -0,0 +1,167 @@
+use crate::lichess::perf_type::PerfType;
+use crate::lichess::user::LightUser;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Detailed statistics of a Lichess user's performance in a single variant,
+/// complementing the summary in [`Performance`][1].
+/// Derived from [lila.perfStat.PerfStat][2].
+/// [1]: crate::lichess::performances::Performance
+/// [2]: <https://github.com/ornicar/lila/blob/master/modules/perfStat/src/main/PerfStat.scala>
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PerfStat {
+    pub perf: PerfStatPerf,
+    // Absent for users who are not ranked in the variant, for example because
+    // their rating is provisional or they have not played recently.
+    #[serde(default)]
+    pub rank: Option<i32>,
+    #[serde(default)]
+    pub percentile: Option<f64>,
+    pub stat: PerfStatDetails,
+}
+
+/// The current Glicko rating of a user in a variant.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PerfStatPerf {
+    pub glicko: Glicko,
+    pub nb: i32,
+    pub progress: i32,
+}
+
+/// Representation of a Glicko rating.
+/// Derived from [lila.rating.Glicko][1].
+/// [1]: <https://github.com/ornicar/lila/blob/master/modules/rating/src/main/Glicko.scala>
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Glicko {
+    pub rating: f64,
+    pub deviation: f64,
+    #[serde(default)]
+    pub provisional: bool,
+}
+
+/// The statistics proper of a [`PerfStat`].
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PerfStatDetails {
+    pub id: String,
+    pub perf_type: PerfStatType,
+    #[serde(default)]
+    pub highest: Option<RatingAt>,
+    #[serde(default)]
+    pub lowest: Option<RatingAt>,
+    pub best_wins: PerfStatResults,
+    pub worst_losses: PerfStatResults,
+    pub count: PerfStatCount,
+    pub result_streak: ResultStreak,
+    pub play_streak: PlayStreak,
+}
+
+/// The perf which a [`PerfStat`] describes.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PerfStatType {
+    pub key: PerfType,
+    pub name: String,
+}
+
+/// A rating reached in a particular game.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RatingAt {
+    #[serde(rename = "int")]
+    pub rating: i32,
+    pub at: DateTime<Utc>,
+    pub game_id: String,
+}
+
+/// A list of notable game results.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PerfStatResults {
+    pub results: Vec<PerfStatResult>,
+}
+
+/// A notable win or loss against a particular opponent.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PerfStatResult {
+    pub op_rating: i32,
+    #[serde(rename = "opId")]
+    pub opponent: LightUser,
+    pub at: DateTime<Utc>,
+    pub game_id: String,
+}
+
+/// Game counts in a variant.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PerfStatCount {
+    pub all: i32,
+    pub rated: i32,
+    pub win: i32,
+    pub loss: i32,
+    pub draw: i32,
+    pub tour: i32,
+    pub berserk: i32,
+    pub op_avg: OpponentAverage,
+    pub seconds: i64,
+    pub disconnects: i32,
+}
+
+impl PerfStatCount {
+    /// The total time spent playing in the variant.
+    pub fn time_played(&self) -> Duration {
+        Duration::seconds(self.seconds)
+    }
+}
+
+/// The average rating of opponents faced, over a population of games.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct OpponentAverage {
+    pub avg: f64,
+    pub pop: i32,
+}
+
+/// The current and longest winning and losing streaks in a variant.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ResultStreak {
+    pub win: Streaks,
+    pub loss: Streaks,
+}
+
+/// The current and longest streaks of continuous play in a variant, measured
+/// both in games and in time.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayStreak {
+    pub nb: Streaks,
+    pub time: Streaks,
+    #[serde(default)]
+    pub last_date: Option<DateTime<Utc>>,
+}
+
+/// A current streak together with the longest streak so far.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Streaks {
+    pub cur: Streak,
+    pub max: Streak,
+}
+
+/// A streak, measured in games or in seconds depending on context, from one
+/// game to another. The bounds are absent for empty streaks.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Streak {
+    pub v: i64,
+    #[serde(default)]
+    pub from: Option<StreakBound>,
+    #[serde(default)]
+    pub to: Option<StreakBound>,
+}
+
+/// A game at the start or end of a [`Streak`].
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct StreakBound {
+    pub at: DateTime<Utc>,
+    pub game_id: String,
+}
@@ -0,0 +1,135 @@
+use crate::lichess::key::ParseKeyError;
+use crate::lichess::speed::Speed;
+use crate::lichess::variant::Variant;
+
+use serde::{Deserialize, Serialize};
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A category in which users are separately rated. Standard games are rated
+/// by speed, and games of any other variant by variant.
+/// Derived from [lila.rating.PerfType][1].
+/// [1]: <https://github.com/ornicar/lila/blob/master/modules/rating/src/main/PerfType.scala>
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum PerfType {
+    UltraBullet,
+    Bullet,
+    Blitz,
+    Rapid,
+    Classical,
+    Correspondence,
+    Chess960,
+    Crazyhouse,
+    Antichess,
+    Atomic,
+    Horde,
+    KingOfTheHill,
+    RacingKings,
+    ThreeCheck,
+    Puzzle,
+}
+
+impl PerfType {
+    pub const ALL: [PerfType; 15] = [
+        PerfType::UltraBullet,
+        PerfType::Bullet,
+        PerfType::Blitz,
+        PerfType::Rapid,
+        PerfType::Classical,
+        PerfType::Correspondence,
+        PerfType::Chess960,
+        PerfType::Crazyhouse,
+        PerfType::Antichess,
+        PerfType::Atomic,
+        PerfType::Horde,
+        PerfType::KingOfTheHill,
+        PerfType::RacingKings,
+        PerfType::ThreeCheck,
+        PerfType::Puzzle,
+    ];
+
+    /// The key by which Lichess identifies the perf, such as "kingOfTheHill".
+    pub fn key(self) -> &'static str {
+        match self {
+            PerfType::UltraBullet => "ultraBullet",
+            PerfType::Bullet => "bullet",
+            PerfType::Blitz => "blitz",
+            PerfType::Rapid => "rapid",
+            PerfType::Classical => "classical",
+            PerfType::Correspondence => "correspondence",
+            PerfType::Chess960 => "chess960",
+            PerfType::Crazyhouse => "crazyhouse",
+            PerfType::Antichess => "antichess",
+            PerfType::Atomic => "atomic",
+            PerfType::Horde => "horde",
+            PerfType::KingOfTheHill => "kingOfTheHill",
+            PerfType::RacingKings => "racingKings",
+            PerfType::ThreeCheck => "threeCheck",
+            PerfType::Puzzle => "puzzle",
+        }
+    }
+
+    /// The name by which Lichess displays the perf, such as "King of the
+    /// Hill".
+    pub fn name(self) -> &'static str {
+        match self {
+            PerfType::UltraBullet => "UltraBullet",
+            PerfType::Bullet => "Bullet",
+            PerfType::Blitz => "Blitz",
+            PerfType::Rapid => "Rapid",
+            PerfType::Classical => "Classical",
+            PerfType::Correspondence => "Correspondence",
+            PerfType::Chess960 => "Chess960",
+            PerfType::Crazyhouse => "Crazyhouse",
+            PerfType::Antichess => "Antichess",
+            PerfType::Atomic => "Atomic",
+            PerfType::Horde => "Horde",
+            PerfType::KingOfTheHill => "King of the Hill",
+            PerfType::RacingKings => "Racing Kings",
+            PerfType::ThreeCheck => "Three-check",
+            PerfType::Puzzle => "Puzzles",
+        }
+    }
+
+    /// The perf in which a game of the given variant and speed is rated.
+    /// Games from a position are rated by speed like standard games.
+    pub fn of(variant: Variant, speed: Speed) -> Self {
+        match variant {
+            Variant::Standard | Variant::FromPosition => match speed {
+                Speed::UltraBullet => PerfType::UltraBullet,
+                Speed::Bullet => PerfType::Bullet,
+                Speed::Blitz => PerfType::Blitz,
+                Speed::Rapid => PerfType::Rapid,
+                Speed::Classical => PerfType::Classical,
+                Speed::Correspondence => PerfType::Correspondence,
+            },
+            Variant::Chess960 => PerfType::Chess960,
+            Variant::Crazyhouse => PerfType::Crazyhouse,
+            Variant::Antichess => PerfType::Antichess,
+            Variant::Atomic => PerfType::Atomic,
+            Variant::Horde => PerfType::Horde,
+            Variant::KingOfTheHill => PerfType::KingOfTheHill,
+            Variant::RacingKings => PerfType::RacingKings,
+            Variant::ThreeCheck => PerfType::ThreeCheck,
+        }
+    }
+}
+
+impl Display for PerfType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+impl FromStr for PerfType {
+    type Err = ParseKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PerfType::ALL
+            .into_iter()
+            .find(|perf| perf.key() == s)
+            .ok_or_else(|| ParseKeyError(s.to_string()))
+    }
+}
@@ -1,4 +1,7 @@
+use crate::lichess::perf_type::PerfType;
+
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::default::Default;
 
 /// Representation of a Lichess user's performances.
@@ -7,33 +10,71 @@ use std::default::Default;
 #[derive(Serialize, Deserialize, Default, Debug)]
 #[serde(rename_all = "camelCase", default)]
 pub struct Performances {
-    chess960: Performance,
-    atomic: Performance,
-    racing_kings: Performance,
-    ultra_bullet: Performance,
-    blitz: Performance,
-    king_of_the_hill: Performance,
-    bullet: Performance,
-    correspondence: Performance,
-    horde: Performance,
-    puzzle: Performance,
-    classical: Performance,
-    rapid: Performance,
-    storm: StormPerformance,
+    pub ultra_bullet: Performance,
+    pub bullet: Performance,
+    pub blitz: Performance,
+    pub rapid: Performance,
+    pub classical: Performance,
+    pub correspondence: Performance,
+    pub chess960: Performance,
+    pub crazyhouse: Performance,
+    pub antichess: Performance,
+    pub atomic: Performance,
+    pub horde: Performance,
+    pub king_of_the_hill: Performance,
+    pub racing_kings: Performance,
+    pub three_check: Performance,
+    pub puzzle: Performance,
+    pub storm: StormPerformance,
+    pub racer: RacerPerformance,
+    pub streak: StreakPerformance,
+
+    // Any perfs which Lichess returns but which are not yet known to this
+    // crate end up here, keyed by perf key, rather than being dropped.
+    #[serde(flatten)]
+    pub other: HashMap<String, serde_json::Value>,
+}
+
+impl Performances {
+    /// The performance in the given perf.
+    pub fn get(&self, perf: PerfType) -> &Performance {
+        match perf {
+            PerfType::UltraBullet => &self.ultra_bullet,
+            PerfType::Bullet => &self.bullet,
+            PerfType::Blitz => &self.blitz,
+            PerfType::Rapid => &self.rapid,
+            PerfType::Classical => &self.classical,
+            PerfType::Correspondence => &self.correspondence,
+            PerfType::Chess960 => &self.chess960,
+            PerfType::Crazyhouse => &self.crazyhouse,
+            PerfType::Antichess => &self.antichess,
+            PerfType::Atomic => &self.atomic,
+            PerfType::Horde => &self.horde,
+            PerfType::KingOfTheHill => &self.king_of_the_hill,
+            PerfType::RacingKings => &self.racing_kings,
+            PerfType::ThreeCheck => &self.three_check,
+            PerfType::Puzzle => &self.puzzle,
+        }
+    }
+
+    /// Iterate over every perf together with the performance in it.
+    pub fn iter(&self) -> impl Iterator<Item = (PerfType, &Performance)> {
+        PerfType::ALL.into_iter().map(move |perf| (perf, self.get(perf)))
+    }
 }
 
-/// Representation of a user's performance in any variant except Puzzle Storm.
+/// Representation of a user's performance in any rated perf.
 /// Derived from [lila.rating.Perf][1].
 /// [1]: <https://github.com/ornicar/lila/blob/master/modules/rating/src/main/Perf.scala>
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Performance {
-    games: u64,
-    rating: u64,
-    rd: u64,
-    prog: i64,
+    pub games: i32,
+    pub rating: i32,
+    pub rd: i32,
+    pub prog: i32,
     #[serde(default)]
-    prov: bool,
+    pub prov: bool,
 }
 
 impl Default for Performance {
@@ -48,18 +89,32 @@ impl Default for Performance {
     }
 }
 
-/// Representation of a user's performance in the Puzzle Storm variant.
+/// Representation of a user's performance in the Puzzle Storm mode.
 /// Derived from [lila.rating.Perf.Storm][1].
 /// [1]: <https://github.com/ornicar/lila/blob/master/modules/rating/src/main/Perf.scala>
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Default, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct StormPerformance {
-    runs: u64,
-    score: u64,
+    pub runs: i32,
+    pub score: i32,
 }
 
-impl Default for StormPerformance {
-    fn default() -> Self {
-        StormPerformance { runs: 0, score: 0 }
-    }
+/// Representation of a user's performance in the Puzzle Racer mode.
+/// Derived from [lila.rating.Perf.Racer][1].
+/// [1]: <https://github.com/ornicar/lila/blob/master/modules/rating/src/main/Perf.scala>
+#[derive(Serialize, Deserialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RacerPerformance {
+    pub runs: i32,
+    pub score: i32,
+}
+
+/// Representation of a user's performance in the Puzzle Streak mode.
+/// Derived from [lila.rating.Perf.Streak][1].
+/// [1]: <https://github.com/ornicar/lila/blob/master/modules/rating/src/main/Perf.scala>
+#[derive(Serialize, Deserialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct StreakPerformance {
+    pub runs: i32,
+    pub score: i32,
 }
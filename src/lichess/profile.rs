@@ -1,18 +1,93 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// Representation of a Lichess user profile.
 /// Derived from [lila.user.Profile][1].
 /// [1]: <https://github.com/ornicar/lila/blob/master/modules/user/src/main/Profile.scala>
-#[derive(Serialize, Deserialize, Default, Debug)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Default, Debug)]
 #[serde(rename_all = "camelCase", default)]
 pub struct Profile {
-    country: Option<String>,
-    location: Option<String>,
-    bio: Option<String>,
-    first_name: Option<String>,
-    last_name: Option<String>,
-    fide_rating: Option<i32>,
-    uscf_rating: Option<i32>,
-    ecf_rating: Option<i32>,
-    links: Option<String>,
+    // The flag shown next to the user's name, as a country code such as
+    // "NL" or a Lichess-specific flag such as "_lichess".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flag: Option<String>,
+    // The country field of profiles created before flags replaced it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bio: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub real_name: Option<String>,
+    // The first name field of profiles created before the real name
+    // replaced it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_name: Option<String>,
+    // The last name field of profiles created before the real name
+    // replaced it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fide_rating: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uscf_rating: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ecf_rating: Option<i32>,
+    // The user's rating from the Russian Chess Federation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rcf_rating: Option<i32>,
+    // The user's rating from the Chess Federation of Canada.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cfc_rating: Option<i32>,
+    // The user's rating from the German Chess Federation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dsb_rating: Option<i32>,
+    // The links in the user's bio, which Lichess sends as a single string
+    // with one link per line.
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        serialize_with = "serialize_links",
+        deserialize_with = "deserialize_links"
+    )]
+    pub links: Vec<String>,
+}
+
+impl Profile {
+    /// The user's real name, joining the legacy first and last names if the
+    /// profile predates the real name field.
+    pub fn name(&self) -> Option<String> {
+        if let Some(real_name) = &self.real_name {
+            return Some(real_name.clone());
+        }
+        let parts: Vec<&str> = [&self.first_name, &self.last_name]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .collect();
+        if parts.is_empty() {
+            None
+        } else {
+            Some(parts.join(" "))
+        }
+    }
+}
+
+fn serialize_links<S>(links: &[String], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&links.join("\r\n"))
+}
+
+fn deserialize_links<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let links = Option::<String>::deserialize(deserializer)?.unwrap_or_default();
+    Ok(links
+        .lines()
+        .map(str::trim)
+        .filter(|link| !link.is_empty())
+        .map(String::from)
+        .collect())
 }
@@ -1,10 +1,16 @@
-use chrono::Utc;
-use chrono::{Date, NaiveDate};
+use crate::lichess::perf_type::PerfType;
 
+use chrono::NaiveDate;
+
+use serde::de::Error;
 use serde::Deserialize;
 use serde::Deserializer;
 
-#[derive(Debug)]
+/// A Lichess user's rating history in every perf, with one record per day on
+/// which their rating changed, in chronological order.
+/// Derived from [lila.history.RatingChartApi][1].
+/// [1]: <https://github.com/ornicar/lila/blob/master/modules/history/src/main/RatingChartApi.scala>
+#[derive(Default, Debug)]
 pub struct RatingHistory {
     pub bullet: Vec<RatingHistoryRecord>,
     pub blitz: Vec<RatingHistoryRecord>,
@@ -23,6 +29,86 @@ pub struct RatingHistory {
     pub ultra_bullet: Vec<RatingHistoryRecord>,
 }
 
+impl RatingHistory {
+    /// The rating history in the given perf.
+    pub fn get(&self, perf: PerfType) -> &[RatingHistoryRecord] {
+        match perf {
+            PerfType::UltraBullet => &self.ultra_bullet,
+            PerfType::Bullet => &self.bullet,
+            PerfType::Blitz => &self.blitz,
+            PerfType::Rapid => &self.rapid,
+            PerfType::Classical => &self.classical,
+            PerfType::Correspondence => &self.correspondence,
+            PerfType::Chess960 => &self.chess960,
+            PerfType::Crazyhouse => &self.crazyhouse,
+            PerfType::Antichess => &self.antichess,
+            PerfType::Atomic => &self.atomic,
+            PerfType::Horde => &self.horde,
+            PerfType::KingOfTheHill => &self.king_of_the_hill,
+            PerfType::RacingKings => &self.racing_kings,
+            PerfType::ThreeCheck => &self.three_check,
+            PerfType::Puzzle => &self.puzzles,
+        }
+    }
+
+    fn get_mut(&mut self, perf: PerfType) -> &mut Vec<RatingHistoryRecord> {
+        match perf {
+            PerfType::UltraBullet => &mut self.ultra_bullet,
+            PerfType::Bullet => &mut self.bullet,
+            PerfType::Blitz => &mut self.blitz,
+            PerfType::Rapid => &mut self.rapid,
+            PerfType::Classical => &mut self.classical,
+            PerfType::Correspondence => &mut self.correspondence,
+            PerfType::Chess960 => &mut self.chess960,
+            PerfType::Crazyhouse => &mut self.crazyhouse,
+            PerfType::Antichess => &mut self.antichess,
+            PerfType::Atomic => &mut self.atomic,
+            PerfType::Horde => &mut self.horde,
+            PerfType::KingOfTheHill => &mut self.king_of_the_hill,
+            PerfType::RacingKings => &mut self.racing_kings,
+            PerfType::ThreeCheck => &mut self.three_check,
+            PerfType::Puzzle => &mut self.puzzles,
+        }
+    }
+
+    /// Iterate over every perf together with the rating history in it.
+    pub fn iter(&self) -> impl Iterator<Item = (PerfType, &[RatingHistoryRecord])> {
+        PerfType::ALL.into_iter().map(move |perf| (perf, self.get(perf)))
+    }
+
+    /// The rating in the given perf at the end of the given date, which is the
+    /// rating of the last record on or before that date. Returns `None` if
+    /// the user had no rating in the perf by then.
+    pub fn rating_at(&self, perf: PerfType, date: NaiveDate) -> Option<i32> {
+        let records = self.get(perf);
+        let i = records.partition_point(|record| record.date <= date);
+        i.checked_sub(1).map(|i| records[i].rating)
+    }
+
+    /// The record with the highest rating in the given perf. If the peak was
+    /// reached more than once, the earliest record is returned.
+    pub fn peak(&self, perf: PerfType) -> Option<&RatingHistoryRecord> {
+        self.get(perf)
+            .iter()
+            .reduce(|peak, record| if record.rating > peak.rating { record } else { peak })
+    }
+
+    /// The change in rating in the given perf between the start of `from` and
+    /// the end of `to`. If the user had no rating in the perf before `from`,
+    /// the change is measured from their first rating in the window. Returns
+    /// `None` if the user had no rating in the perf during the window.
+    pub fn rating_change(&self, perf: PerfType, from: NaiveDate, to: NaiveDate) -> Option<i32> {
+        let records = self.get(perf);
+        let start = records.partition_point(|record| record.date < from);
+        let before = match start.checked_sub(1) {
+            Some(i) => records[i].rating,
+            None => records.first().filter(|record| record.date <= to)?.rating,
+        };
+        let after = self.rating_at(perf, to)?;
+        Some(after - before)
+    }
+}
+
 impl<'de> Deserialize<'de> for RatingHistory {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -33,32 +119,27 @@ impl<'de> Deserialize<'de> for RatingHistory {
             name: String,
             points: Vec<RatingHistoryRecord>,
         }
-        let mut arr = Vec::<NamedHistory>::deserialize(deserializer)?;
-        arr.reverse();
-        Ok(RatingHistory {
-            bullet: arr.pop().unwrap().points,
-            blitz: arr.pop().unwrap().points,
-            rapid: arr.pop().unwrap().points,
-            classical: arr.pop().unwrap().points,
-            correspondence: arr.pop().unwrap().points,
-            chess960: arr.pop().unwrap().points,
-            king_of_the_hill: arr.pop().unwrap().points,
-            three_check: arr.pop().unwrap().points,
-            antichess: arr.pop().unwrap().points,
-            atomic: arr.pop().unwrap().points,
-            horde: arr.pop().unwrap().points,
-            racing_kings: arr.pop().unwrap().points,
-            crazyhouse: arr.pop().unwrap().points,
-            puzzles: arr.pop().unwrap().points,
-            ultra_bullet: arr.pop().unwrap().points,
-        })
+
+        // Entries are matched to perfs by name rather than by position, so
+        // that entries which are missing, reordered, or for perfs unknown to
+        // this crate cannot mislabel the others.
+        let mut history = RatingHistory::default();
+        for entry in Vec::<NamedHistory>::deserialize(deserializer)? {
+            if let Some(perf) = PerfType::ALL.into_iter().find(|perf| perf.name() == entry.name) {
+                let mut points = entry.points;
+                points.sort_by_key(|record| record.date);
+                *history.get_mut(perf) = points;
+            }
+        }
+        Ok(history)
     }
 }
 
-#[derive(Debug)]
+/// A user's rating in a perf at the end of a day.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct RatingHistoryRecord {
     pub rating: i32,
-    pub date: Date<Utc>,
+    pub date: NaiveDate,
 }
 
 impl<'de> Deserialize<'de> for RatingHistoryRecord {
@@ -66,15 +147,19 @@ impl<'de> Deserialize<'de> for RatingHistoryRecord {
     where
         D: Deserializer<'de>,
     {
+        // Records are serialized as [year, month, day, rating], where the
+        // month is zero-based.
         let arr = <[i32; 4]>::deserialize(deserializer)?;
-        let date = NaiveDate::from_ymd(
-            arr[0],
-            (arr[1] + 1).try_into().unwrap(),
-            arr[2].try_into().unwrap(),
-        );
+        let invalid = || D::Error::custom(format!("invalid rating history date {:?}", arr));
+        let month = arr[1]
+            .checked_add(1)
+            .and_then(|month| u32::try_from(month).ok())
+            .ok_or_else(invalid)?;
+        let day = u32::try_from(arr[2]).map_err(|_| invalid())?;
+        let date = NaiveDate::from_ymd_opt(arr[0], month, day).ok_or_else(invalid)?;
         Ok(RatingHistoryRecord {
             rating: arr[3],
-            date: Date::from_utc(date, Utc),
+            date,
         })
     }
 }
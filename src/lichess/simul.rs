@@ -0,0 +1,75 @@
+use crate::lichess::key::Key;
+use crate::lichess::user::LightUser;
+use crate::lichess::variant::Variant;
+
+use chrono::serde::ts_milliseconds_option;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Lists of the simuls on Lichess, by stage.
+/// Derived from [lila.simul.JsonView][1].
+/// [1]: <https://github.com/ornicar/lila/blob/master/modules/simul/src/main/JsonView.scala>
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Simuls {
+    // Simuls created by the authenticated user which have not yet started.
+    // Always empty for unauthenticated clients.
+    #[serde(default)]
+    pub pending: Vec<Simul>,
+    #[serde(default)]
+    pub created: Vec<Simul>,
+    #[serde(default)]
+    pub started: Vec<Simul>,
+    #[serde(default)]
+    pub finished: Vec<Simul>,
+}
+
+/// A simultaneous exhibition, in which a host plays many players at once.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Simul {
+    pub id: String,
+    pub name: String,
+    pub full_name: String,
+    pub host: SimulHost,
+    pub variants: Vec<SimulVariant>,
+    pub is_created: bool,
+    pub is_running: bool,
+    pub is_finished: bool,
+    #[serde(default)]
+    pub text: Option<String>,
+    pub nb_applicants: i32,
+    pub nb_pairings: i32,
+    #[serde(default, with = "ts_milliseconds_option")]
+    pub estimated_start_at: Option<DateTime<Utc>>,
+    #[serde(default, with = "ts_milliseconds_option")]
+    pub started_at: Option<DateTime<Utc>>,
+    #[serde(default, with = "ts_milliseconds_option")]
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// The host of a [`Simul`], with their rating at the time the simul was
+/// created.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulHost {
+    #[serde(flatten)]
+    pub user: LightUser,
+    #[serde(default)]
+    pub rating: Option<i32>,
+    #[serde(default)]
+    pub provisional: bool,
+    // The id of the game the host is currently playing, if any.
+    #[serde(default)]
+    pub game_id: Option<String>,
+    #[serde(default)]
+    pub online: bool,
+}
+
+/// A variant played in a [`Simul`].
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SimulVariant {
+    pub key: Key<Variant>,
+    pub name: String,
+    #[serde(default)]
+    pub icon: Option<String>,
+}
@@ -0,0 +1,60 @@
+use crate::lichess::key::ParseKeyError;
+
+use serde::{Deserialize, Serialize};
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// The time control category of a game, determined by its estimated duration.
+/// Derived from [chess.Speed][1].
+/// [1]: <https://github.com/lichess-org/scalachess/blob/master/core/src/main/scala/Speed.scala>
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum Speed {
+    UltraBullet,
+    Bullet,
+    Blitz,
+    Rapid,
+    Classical,
+    Correspondence,
+}
+
+impl Speed {
+    pub const ALL: [Speed; 6] = [
+        Speed::UltraBullet,
+        Speed::Bullet,
+        Speed::Blitz,
+        Speed::Rapid,
+        Speed::Classical,
+        Speed::Correspondence,
+    ];
+
+    /// The key by which Lichess identifies the speed, such as "ultraBullet".
+    pub fn key(self) -> &'static str {
+        match self {
+            Speed::UltraBullet => "ultraBullet",
+            Speed::Bullet => "bullet",
+            Speed::Blitz => "blitz",
+            Speed::Rapid => "rapid",
+            Speed::Classical => "classical",
+            Speed::Correspondence => "correspondence",
+        }
+    }
+}
+
+impl Display for Speed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+impl FromStr for Speed {
+    type Err = ParseKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Speed::ALL
+            .into_iter()
+            .find(|speed| speed.key() == s)
+            .ok_or_else(|| ParseKeyError(s.to_string()))
+    }
+}
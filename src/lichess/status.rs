@@ -0,0 +1,31 @@
+use crate::lichess::title::Title;
+
+use serde::{Deserialize, Serialize};
+
+/// Representation of a Lichess user's real-time status.
+/// Derived from [lila.api.UserApi][1].
+/// [1]: <https://github.com/ornicar/lila/blob/master/app/controllers/User.scala>
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UserStatus {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub title: Option<Title>,
+    #[serde(default)]
+    pub online: bool,
+    #[serde(default)]
+    pub playing: bool,
+    #[serde(default)]
+    pub streaming: bool,
+    #[serde(default)]
+    pub patron: bool,
+    // Only present when requested with the withSignal parameter, and only for
+    // users who are online. Ranges from 1 (poor) to 4 (excellent).
+    #[serde(default)]
+    pub signal: Option<u8>,
+    // Only present when requested with the withGameIds parameter, and only
+    // for users who are playing.
+    #[serde(default)]
+    pub playing_id: Option<String>,
+}
@@ -0,0 +1,31 @@
+use chrono::serde::ts_milliseconds;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Metadata of a Lichess study, without its chapters.
+/// Derived from [lila.study.StudyApi][1].
+/// [1]: <https://github.com/ornicar/lila/blob/master/modules/study/src/main/StudyApi.scala>
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct StudyMetadata {
+    pub id: String,
+    pub name: String,
+    #[serde(with = "ts_milliseconds")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "ts_milliseconds")]
+    pub updated_at: DateTime<Utc>,
+}
+
+/// The chapters created by importing PGN into a study, one per game in the
+/// PGN.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ImportedChapters {
+    pub chapters: Vec<ImportedChapter>,
+}
+
+/// A chapter created by importing PGN into a study.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ImportedChapter {
+    pub id: String,
+    pub name: String,
+}
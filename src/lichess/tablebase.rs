@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+
+/// The result of looking up a position in an endgame tablebase. DTZ is the
+/// number of plies until the next capture or pawn move under optimal play, and
+/// DTM the number of plies until mate. Both are positive when the side to move
+/// is winning and negative when it is losing, and are absent when unknown.
+/// Derived from [lila-tablebase][1].
+/// [1]: <https://github.com/lichess-org/lila-tablebase/blob/master/src/main.rs>
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TablebaseResult {
+    pub category: TablebaseCategory,
+    #[serde(default)]
+    pub dtz: Option<i32>,
+    #[serde(default)]
+    pub precise_dtz: Option<i32>,
+    #[serde(default)]
+    pub dtm: Option<i32>,
+    #[serde(default)]
+    pub checkmate: bool,
+    #[serde(default)]
+    pub stalemate: bool,
+    #[serde(default)]
+    pub variant_win: bool,
+    #[serde(default)]
+    pub variant_loss: bool,
+    #[serde(default)]
+    pub insufficient_material: bool,
+    // Sorted from best to worst for the side to move.
+    pub moves: Vec<TablebaseMove>,
+}
+
+impl TablebaseResult {
+    /// The best move for the side to move, if there are any legal moves.
+    pub fn best_move(&self) -> Option<&TablebaseMove> {
+        self.moves.first()
+    }
+}
+
+/// A legal move from a tablebase position, evaluated from the perspective of
+/// the side to move after the move is played.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TablebaseMove {
+    pub uci: String,
+    pub san: String,
+    pub category: TablebaseCategory,
+    #[serde(default)]
+    pub dtz: Option<i32>,
+    #[serde(default)]
+    pub precise_dtz: Option<i32>,
+    #[serde(default)]
+    pub dtm: Option<i32>,
+    #[serde(default)]
+    pub zeroing: bool,
+    #[serde(default)]
+    pub checkmate: bool,
+    #[serde(default)]
+    pub stalemate: bool,
+    #[serde(default)]
+    pub variant_win: bool,
+    #[serde(default)]
+    pub variant_loss: bool,
+    #[serde(default)]
+    pub insufficient_material: bool,
+}
+
+/// The outcome of a tablebase position under optimal play. Cursed wins and
+/// blessed losses are wins and losses which are drawn by the fifty-move rule.
+/// Maybe wins and maybe losses are results whose DTZ may be off by one, so the
+/// fifty-move rule may or may not apply.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum TablebaseCategory {
+    Win,
+    SyzygyWin,
+    MaybeWin,
+    CursedWin,
+    Draw,
+    BlessedLoss,
+    MaybeLoss,
+    SyzygyLoss,
+    Loss,
+    Unknown,
+}
+
+impl TablebaseCategory {
+    /// Whether the outcome is a win which cannot be claimed as a draw under
+    /// the fifty-move rule.
+    pub fn is_win(self) -> bool {
+        matches!(self, TablebaseCategory::Win | TablebaseCategory::SyzygyWin)
+    }
+
+    /// Whether the outcome is a loss which cannot be claimed as a draw under
+    /// the fifty-move rule.
+    pub fn is_loss(self) -> bool {
+        matches!(self, TablebaseCategory::Loss | TablebaseCategory::SyzygyLoss)
+    }
+
+    /// Whether the outcome is drawn, including wins and losses drawn by the
+    /// fifty-move rule.
+    pub fn is_draw(self) -> bool {
+        matches!(
+            self,
+            TablebaseCategory::Draw | TablebaseCategory::CursedWin | TablebaseCategory::BlessedLoss
+        )
+    }
+}
@@ -1,9 +1,12 @@
 use serde::{Deserialize, Serialize};
 
+use std::fmt::Display;
+
 /// Representation of a Lichess user's title.
 /// Derived from [lila.user.Title][1].
 /// [1]: <https://github.com/ornicar/lila/blob/master/modules/user/src/main/Title.scala>
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Hash, Debug)]
+#[serde(from = "String", into = "String")]
 pub enum Title {
     GM,
     WGM,
@@ -17,4 +20,108 @@ pub enum Title {
     WNM,
     LM,
     BOT,
+    /// A title unknown to this crate, holding the code Lichess sent, so that
+    /// titles added after this crate was released do not fail to deserialize.
+    Other(String),
+}
+
+impl Title {
+    pub const ALL: [Title; 12] = [
+        Title::GM,
+        Title::WGM,
+        Title::IM,
+        Title::WIM,
+        Title::FM,
+        Title::WFM,
+        Title::CM,
+        Title::WCM,
+        Title::NM,
+        Title::WNM,
+        Title::LM,
+        Title::BOT,
+    ];
+
+    /// The code by which Lichess identifies the title, such as "WGM".
+    pub fn code(&self) -> &str {
+        match self {
+            Title::GM => "GM",
+            Title::WGM => "WGM",
+            Title::IM => "IM",
+            Title::WIM => "WIM",
+            Title::FM => "FM",
+            Title::WFM => "WFM",
+            Title::CM => "CM",
+            Title::WCM => "WCM",
+            Title::NM => "NM",
+            Title::WNM => "WNM",
+            Title::LM => "LM",
+            Title::BOT => "BOT",
+            Title::Other(code) => code,
+        }
+    }
+
+    /// The full name of the title, such as "Woman Grandmaster". Unknown
+    /// titles are named by their code.
+    pub fn name(&self) -> &str {
+        match self {
+            Title::GM => "Grandmaster",
+            Title::WGM => "Woman Grandmaster",
+            Title::IM => "International Master",
+            Title::WIM => "Woman International Master",
+            Title::FM => "FIDE Master",
+            Title::WFM => "Woman FIDE Master",
+            Title::CM => "Candidate Master",
+            Title::WCM => "Woman Candidate Master",
+            Title::NM => "National Master",
+            Title::WNM => "Woman National Master",
+            Title::LM => "Lichess Master",
+            Title::BOT => "Chess Robot",
+            Title::Other(code) => code,
+        }
+    }
+
+    /// Whether the title marks a bot account rather than a person.
+    pub fn is_bot(&self) -> bool {
+        *self == Title::BOT
+    }
+
+    /// Whether the title is a chess title awarded by FIDE or a national
+    /// federation. Lichess masters, bots and titles unknown to this crate are
+    /// not.
+    pub fn is_titled(&self) -> bool {
+        matches!(
+            self,
+            Title::GM
+                | Title::WGM
+                | Title::IM
+                | Title::WIM
+                | Title::FM
+                | Title::WFM
+                | Title::CM
+                | Title::WCM
+                | Title::NM
+                | Title::WNM
+        )
+    }
+}
+
+impl From<String> for Title {
+    fn from(code: String) -> Self {
+        Title::ALL
+            .into_iter()
+            .find(|title| title.code() == code)
+            .unwrap_or(Title::Other(code))
+    }
+}
+
+impl From<Title> for String {
+    fn from(title: Title) -> Self {
+        title.code().to_string()
+    }
+}
+
+impl Display for Title {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code())
+    }
 }
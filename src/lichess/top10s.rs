@@ -1,8 +1,12 @@
+use crate::lichess::perf_type::PerfType;
 use crate::lichess::title::Title;
 use crate::lichess::user::LightUser;
 
+use serde::de::Error;
 use serde::{Deserialize, Deserializer};
 
+use std::collections::HashMap;
+
 /// Lists of the top 10 users in all variants.
 /// Derived from [lila.user.Perfs.Leaderboards][1].
 /// [1]: <https://github.com/ornicar/lila/blob/master/modules/user/src/main/Perfs.scala>
@@ -24,6 +28,28 @@ pub struct Top10s {
     pub three_check: [Top10; 10],
 }
 
+impl Top10s {
+    /// The top 10 list in the given perf, if there is one.
+    pub fn get(&self, perf: PerfType) -> Option<&[Top10; 10]> {
+        match perf {
+            PerfType::UltraBullet => Some(&self.ultra_bullet),
+            PerfType::Bullet => Some(&self.bullet),
+            PerfType::Blitz => Some(&self.blitz),
+            PerfType::Rapid => Some(&self.rapid),
+            PerfType::Classical => Some(&self.classical),
+            PerfType::Chess960 => Some(&self.chess960),
+            PerfType::Crazyhouse => Some(&self.crazyhouse),
+            PerfType::Antichess => Some(&self.antichess),
+            PerfType::Atomic => Some(&self.atomic),
+            PerfType::Horde => Some(&self.horde),
+            PerfType::KingOfTheHill => Some(&self.king_of_the_hill),
+            PerfType::RacingKings => Some(&self.racing_kings),
+            PerfType::ThreeCheck => Some(&self.three_check),
+            PerfType::Correspondence | PerfType::Puzzle => None,
+        }
+    }
+}
+
 /// A user in a Top 10 list, with their performance in the relevant variant.
 /// Derived from [lila.user.Perfs.Leaderboards][1].
 /// [1]: <https://github.com/ornicar/lila/blob/master/modules/user/src/main/Perfs.scala>
@@ -83,35 +109,18 @@ pub struct Top10Performance {
     pub progress: i32,
 }
 
-// This is kind of a hack. The object returned by the Top 10 API endpoint has
-// a perfs field with a single perf nested inside, corresponding to the user's
-// performance in the variant in which they are in the top 10. Since this field
-// is always of the same type, and the performance's variant is encoded in the
-// name of the list which the Top10 is in, we remove the outer struct on
+// The object returned by the Top 10 API endpoint has a perfs field with a
+// single perf nested inside, keyed by the perf in which the user is in the top
+// 10. Since this field is always of the same type, and the perf is encoded in
+// the name of the list which the Top10 is in, we remove the outer map on
 // deserialization.
 fn deserialize<'de, D>(deserializer: D) -> Result<Top10Performance, D::Error>
 where
     D: Deserializer<'de>,
 {
-    #[derive(Deserialize, Debug)]
-    struct WrappedTop10Performance {
-        #[serde(
-            alias = "blitz",
-            alias = "rapid",
-            alias = "classical",
-            alias = "ultraBullet",
-            alias = "chess960",
-            alias = "crazyhouse",
-            alias = "antichess",
-            alias = "atomic",
-            alias = "horde",
-            alias = "kingOfTheHill",
-            alias = "racingKings",
-            alias = "threeCheck"
-        )]
-        bullet: Top10Performance,
-    }
-
-    let perf = WrappedTop10Performance::deserialize(deserializer)?;
-    Ok(perf.bullet)
+    let perfs = HashMap::<PerfType, Top10Performance>::deserialize(deserializer)?;
+    perfs
+        .into_values()
+        .next()
+        .ok_or_else(|| D::Error::custom("expected a single perf"))
 }
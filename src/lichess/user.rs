@@ -51,39 +51,20 @@ pub struct User {
     pub verified: bool,
     pub play_time: PlayTime,
     #[serde(default)]
-    title: Option<Title>,
+    pub title: Option<Title>,
 }
 
-/// Extended representation of a Lichess user.
+/// Extended representation of a Lichess user, as returned when a single user
+/// is requested. It holds everything in [`User`] along with fields that
+/// Lichess only computes for a single user.
 /// Derived from [lila.user.User][1]. User and ExtendedUser result from
 /// different serializations of the same object.
 /// [1]: <https://github.com/ornicar/lila/blob/master/modules/user/src/main/User.scala>
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ExtendedUser {
-    pub id: String,
-    pub username: String,
-    #[serde(default)]
-    pub online: bool,
-    #[serde(default)]
-    pub perfs: Performances,
-    #[serde(with = "ts_milliseconds")]
-    pub created_at: DateTime<Utc>,
-    #[serde(default)]
-    pub disabled: bool,
-    #[serde(default)]
-    pub tos_violation: bool,
-    #[serde(default)]
-    pub profile: Profile,
-    #[serde(with = "ts_milliseconds")]
-    pub seen_at: DateTime<Utc>,
-    #[serde(default)]
-    pub patron: bool,
-    #[serde(default)]
-    pub verified: bool,
-    pub play_time: PlayTime,
-    #[serde(default)]
-    pub title: Option<Title>,
+    #[serde(flatten)]
+    pub user: User,
     pub url: String,
     #[serde(default)]
     pub playing: Option<String>,
@@ -108,6 +89,29 @@ pub struct ExtendedUser {
     pub follows_you: Option<bool>,
 }
 
+impl From<ExtendedUser> for User {
+    fn from(extended: ExtendedUser) -> Self {
+        extended.user
+    }
+}
+
+impl From<User> for LightUser {
+    fn from(user: User) -> Self {
+        LightUser {
+            name: user.username,
+            title: user.title,
+            patron: user.patron,
+            id: user.id,
+        }
+    }
+}
+
+impl From<ExtendedUser> for LightUser {
+    fn from(extended: ExtendedUser) -> Self {
+        extended.user.into()
+    }
+}
+
 // This is something of an ugly hack. Option<bool>::default() is None, but when
 // deserializing Users we need it to be Some(false), as when the field is
 // absent the value ought to default to false like all other booleans, and only
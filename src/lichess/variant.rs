@@ -0,0 +1,98 @@
+use crate::lichess::key::ParseKeyError;
+
+use serde::{Deserialize, Serialize};
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// The rules by which a game is played.
+/// Derived from [chess.variant.Variant][1].
+/// [1]: <https://github.com/lichess-org/scalachess/blob/master/core/src/main/scala/variant/Variant.scala>
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum Variant {
+    Standard,
+    Chess960,
+    Crazyhouse,
+    Antichess,
+    Atomic,
+    Horde,
+    KingOfTheHill,
+    RacingKings,
+    ThreeCheck,
+    FromPosition,
+}
+
+impl Variant {
+    pub const ALL: [Variant; 10] = [
+        Variant::Standard,
+        Variant::Chess960,
+        Variant::Crazyhouse,
+        Variant::Antichess,
+        Variant::Atomic,
+        Variant::Horde,
+        Variant::KingOfTheHill,
+        Variant::RacingKings,
+        Variant::ThreeCheck,
+        Variant::FromPosition,
+    ];
+
+    /// The key by which Lichess identifies the variant, such as
+    /// "kingOfTheHill".
+    pub fn key(self) -> &'static str {
+        match self {
+            Variant::Standard => "standard",
+            Variant::Chess960 => "chess960",
+            Variant::Crazyhouse => "crazyhouse",
+            Variant::Antichess => "antichess",
+            Variant::Atomic => "atomic",
+            Variant::Horde => "horde",
+            Variant::KingOfTheHill => "kingOfTheHill",
+            Variant::RacingKings => "racingKings",
+            Variant::ThreeCheck => "threeCheck",
+            Variant::FromPosition => "fromPosition",
+        }
+    }
+
+    /// The name by which UCI engines identify the variant, such as
+    /// "kingofthehill". Games from a position are played by standard rules.
+    pub fn uci(self) -> &'static str {
+        match self {
+            Variant::Standard | Variant::Chess960 | Variant::FromPosition => "chess",
+            Variant::Crazyhouse => "crazyhouse",
+            Variant::Antichess => "antichess",
+            Variant::Atomic => "atomic",
+            Variant::Horde => "horde",
+            Variant::KingOfTheHill => "kingofthehill",
+            Variant::RacingKings => "racingkings",
+            Variant::ThreeCheck => "3check",
+        }
+    }
+
+    /// The variant named by a UCI engine, such as "kingofthehill". As
+    /// Chess960 and games from a position share the name "chess" with
+    /// standard games, that name is parsed as [`Variant::Standard`].
+    pub fn from_uci(name: &str) -> Result<Self, ParseKeyError> {
+        Variant::ALL
+            .into_iter()
+            .find(|variant| variant.uci() == name)
+            .ok_or_else(|| ParseKeyError(name.to_string()))
+    }
+}
+
+impl Display for Variant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.key())
+    }
+}
+
+impl FromStr for Variant {
+    type Err = ParseKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Variant::ALL
+            .into_iter()
+            .find(|variant| variant.key() == s)
+            .ok_or_else(|| ParseKeyError(s.to_string()))
+    }
+}
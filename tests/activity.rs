@@ -0,0 +1,62 @@
+use rust_lichess_wrapper::lichess::activity::{Activity, ActivityPerf};
+use rust_lichess_wrapper::lichess::game::Color;
+use rust_lichess_wrapper::lichess::perf_type::PerfType;
+
+const ACTIVITY: &str = include_str!("fixtures/activity.json");
+
+#[test]
+fn activity() {
+    let activity: Vec<Activity> = serde_json::from_str(ACTIVITY).unwrap();
+    assert_eq!(activity.len(), 2);
+
+    let day = &activity[0];
+    assert_eq!(day.games[&PerfType::Blitz.into()].win, 3);
+    assert_eq!(day.games[&PerfType::Atomic.into()].rp.as_ref().unwrap().diff(), -29);
+    assert_eq!(day.tournaments.as_ref().unwrap().best[0].rank, 4);
+    assert!(day.stream);
+
+    let moves = day.correspondence_moves.as_ref().unwrap();
+    assert_eq!(moves.games[0].color, Color::Black);
+    assert_eq!(moves.games[0].opponent.user.id, "mary");
+    assert_eq!(moves.games[0].opponent.user.name, "Mary");
+    assert_eq!(moves.games[0].opponent.rating, Some(1545));
+
+    let follows = day.follows.as_ref().unwrap();
+    let followers = follows.followers.as_ref().unwrap();
+    assert_eq!(followers.users.len(), 2);
+    assert_eq!(followers.users[1].id, "thibault");
+    assert_eq!(followers.nb, Some(5));
+    assert!(follows.following.as_ref().unwrap().nb.is_none());
+
+    let ends = activity[1].correspondence_ends.as_ref().unwrap();
+    assert_eq!(ends.games[0].color, Color::White);
+    assert_eq!(ends.games[0].opponent.rating, None);
+    assert!(activity[1].games.is_empty());
+}
+
+#[test]
+fn activity_round_trip() {
+    let activity: Vec<Activity> = serde_json::from_str(ACTIVITY).unwrap();
+    let serialized = serde_json::to_value(&activity).unwrap();
+    let reparsed: Vec<Activity> = serde_json::from_value(serialized.clone()).unwrap();
+    assert_eq!(serialized, serde_json::to_value(&reparsed).unwrap());
+}
+
+#[test]
+fn unknown_perf() {
+    let json = r#"{
+        "interval": { "start": 1659312000000, "end": 1659398400000 },
+        "games": {
+            "blitz": { "win": 1, "loss": 0, "draw": 0, "rp": { "before": 1500, "after": 1508 } },
+            "duckChess": { "win": 0, "loss": 1, "draw": 0, "rp": { "before": 1500, "after": 1492 } }
+        }
+    }"#;
+    let activity: Activity = serde_json::from_str(json).unwrap();
+    assert_eq!(activity.games.len(), 2);
+    assert_eq!(activity.games[&PerfType::Blitz.into()].win, 1);
+    assert_eq!(activity.games[&ActivityPerf::Other("duckChess".to_string())].loss, 1);
+
+    let round_trip = serde_json::to_value(&activity).unwrap();
+    assert_eq!(round_trip["games"]["duckChess"]["rp"]["after"], 1492);
+    assert_eq!(round_trip["games"]["blitz"]["win"], 1);
+}
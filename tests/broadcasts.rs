@@ -0,0 +1,143 @@
+use rust_lichess_wrapper::core::api::broadcasts::{BroadcastRoundSettings, BroadcastTourSettings};
+use rust_lichess_wrapper::core::mock::{MockResponse, MockServer};
+
+use chrono::{TimeZone, Utc};
+use futures::StreamExt;
+use hyper::Method;
+
+use std::time::Duration;
+
+fn tour() -> serde_json::Value {
+    serde_json::json!({
+        "id": "ZmFL4cKw",
+        "name": "Club Championship",
+        "slug": "club-championship",
+        "description": "Weekly rounds",
+        "createdAt": 1659448421000i64,
+        "url": "https://lichess.org/broadcast/club-championship/ZmFL4cKw",
+    })
+}
+
+fn round() -> serde_json::Value {
+    serde_json::json!({
+        "id": "Wt7hXRa3",
+        "name": "Round 1",
+        "slug": "round-1",
+        "createdAt": 1659448500000i64,
+        "startsAt": 1659520800000i64,
+        "url": "https://lichess.org/broadcast/club-championship/round-1/Wt7hXRa3",
+    })
+}
+
+fn form(body: &[u8]) -> Vec<String> {
+    String::from_utf8(body.to_vec()).unwrap().split('&').map(String::from).collect()
+}
+
+#[tokio::test]
+async fn official_broadcasts() {
+    let server = MockServer::start().await.unwrap();
+    let broadcast = serde_json::json!({ "tour": tour(), "rounds": [round()] }).to_string();
+    server.route(Method::GET, "/api/broadcast", MockResponse::ndjson(&broadcast, Duration::ZERO));
+    let client = server.client();
+
+    let broadcasts: Vec<_> = client
+        .get_official_broadcasts(20)
+        .await
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+    assert_eq!(broadcasts.len(), 1);
+    assert_eq!(broadcasts[0].tour.slug, "club-championship");
+    assert_eq!(broadcasts[0].tour.tier, None);
+    assert_eq!(broadcasts[0].rounds[0].id, "Wt7hXRa3");
+    assert!(!broadcasts[0].rounds[0].ongoing);
+    assert_eq!(server.requests()[0].query.as_deref(), Some("nb=20"));
+}
+
+#[tokio::test]
+async fn create() {
+    let server = MockServer::start().await.unwrap();
+    server.route(
+        Method::POST,
+        "/broadcast/new",
+        MockResponse::json(&serde_json::json!({ "tour": tour() }).to_string()),
+    );
+    server.route(
+        Method::POST,
+        "/broadcast/ZmFL4cKw/new",
+        MockResponse::json(&serde_json::json!({ "round": round(), "tour": tour() }).to_string()),
+    );
+    let client = server.client_with_token("lip_secret");
+
+    let tour_settings = BroadcastTourSettings {
+        name: "Club Championship".to_string(),
+        description: "Weekly rounds".to_string(),
+        markdown: None,
+        auto_leaderboard: true,
+    };
+    let broadcast = client.create_broadcast_tour(&tour_settings).await.unwrap();
+    assert_eq!(broadcast.tour.id, "ZmFL4cKw");
+    assert!(broadcast.rounds.is_empty());
+
+    let round_settings = BroadcastRoundSettings {
+        name: "Round 1".to_string(),
+        sync_url: None,
+        starts_at: Some(Utc.timestamp_millis_opt(1659520800000).unwrap()),
+    };
+    let created = client.create_broadcast_round("ZmFL4cKw", &round_settings).await.unwrap();
+    assert_eq!(created.round.starts_at, round_settings.starts_at);
+    assert_eq!(created.tour.name, "Club Championship");
+
+    let requests = server.requests();
+    assert_eq!(
+        form(&requests[0].body),
+        ["name=Club%20Championship", "description=Weekly%20rounds", "autoLeaderboard=true"]
+    );
+    assert_eq!(form(&requests[1].body), ["name=Round%201", "startsAt=1659520800000"]);
+}
+
+#[tokio::test]
+async fn push() {
+    let server = MockServer::start().await.unwrap();
+    server.route(
+        Method::POST,
+        "/api/broadcast/round/Wt7hXRa3/push",
+        MockResponse::json(
+            r#"{"games":[{"tags":{"White":"Georges","Black":"Mary"},"moves":3},{"tags":{},"error":"No moves"}]}"#,
+        ),
+    );
+    let client = server.client_with_token("lip_secret");
+
+    let pgn = "[White \"Georges\"]\n[Black \"Mary\"]\n\n1. e4 e5 2. Nf3 *\n\n[White \"?\"]\n\n*\n";
+    let result = client.push_broadcast_pgn("Wt7hXRa3", pgn).await.unwrap();
+    assert_eq!(result.games[0].tags["White"], "Georges");
+    assert_eq!(result.games[0].moves, 3);
+    assert_eq!(result.games[1].moves, 0);
+    assert_eq!(result.games[1].error.as_deref(), Some("No moves"));
+
+    let request = &server.requests()[0];
+    assert_eq!(request.body, pgn);
+    assert_eq!(request.headers["content-type"], "text/plain");
+}
+
+#[tokio::test]
+async fn round_pgn() {
+    let server = MockServer::start().await.unwrap();
+    let games = "[White \"Georges\"]\n\n1. e4 *\n\n\n[White \"Mary\"]\n\n1. d4 *\n\n\n";
+    server.route(Method::GET, "/api/stream/broadcast/round/Wt7hXRa3.pgn", MockResponse::pgn(games));
+    server.route(Method::GET, "/api/broadcast/round/Wt7hXRa3.pgn", MockResponse::pgn(games));
+    server.route(Method::GET, "/api/broadcast/ZmFL4cKw.pgn", MockResponse::pgn(games));
+    let client = server.client();
+
+    let streamed: Vec<String> = client
+        .stream_broadcast_round("Wt7hXRa3")
+        .await
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+    assert_eq!(streamed, ["[White \"Georges\"]\n\n1. e4 *", "[White \"Mary\"]\n\n1. d4 *"]);
+    assert_eq!(client.export_broadcast_round("Wt7hXRa3").await.unwrap(), games);
+    assert_eq!(client.export_broadcast_tour("ZmFL4cKw").await.unwrap(), games);
+}
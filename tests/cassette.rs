@@ -0,0 +1,237 @@
+use rust_lichess_wrapper::core::api::games::GameExportOptions;
+use rust_lichess_wrapper::core::cassette::{Cassette, Chunk, Interaction};
+use rust_lichess_wrapper::core::client::{Client, ClientError};
+use rust_lichess_wrapper::core::mock::{fixtures, MockResponse, MockServer};
+use rust_lichess_wrapper::lichess::game::Game;
+
+use futures::StreamExt;
+use hyper::Method;
+use tokio::time::Instant;
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+fn cassette_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("lichess-cassette-{}-{}.json", name, std::process::id()))
+}
+
+async fn export_games(client: &Client) -> Vec<Game> {
+    client
+        .export_user_games("georges", &GameExportOptions::default())
+        .await
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+        .await
+}
+
+fn replay_client(url: &str, path: &Path, realtime: bool) -> Client {
+    let mut client = Client::new();
+    client.set_lichess_host(url);
+    client.replay_cassette(path, realtime).unwrap();
+    client
+}
+
+/// Record the fixture routes, and return the URL the server was at.
+async fn record_fixtures(path: &Path) -> String {
+    let server = MockServer::with_fixtures().await.unwrap();
+    server.route_once(Method::GET, "/api/users/status", MockResponse::rate_limited());
+
+    let mut client = server.client_with_token("lip_secret");
+    client.record_cassette(path).unwrap();
+    assert_eq!(export_games(&client).await.len(), 2);
+    let err = client.get_user_statuses(&["georges"], false, false).await.unwrap_err();
+    assert!(matches!(err.downcast_ref(), Some(ClientError::RateLimited(_))));
+
+    client.flush_cassette().await.unwrap();
+    server.url()
+}
+
+#[tokio::test]
+async fn record() {
+    let path = cassette_path("record");
+    let url = record_fixtures(&path).await;
+
+    let cassette = Cassette::load(&path).unwrap();
+    assert_eq!(cassette.interactions.len(), 2);
+
+    let games = &cassette.interactions[0];
+    assert_eq!(games.method, "GET");
+    assert!(games.uri.starts_with(&format!("{}/api/games/user/georges?", url)));
+    assert_eq!(games.status, 200);
+    assert_eq!(games.error, None);
+    let body: Vec<u8> = games.chunks.iter().flat_map(|chunk| chunk.data.clone()).collect();
+    assert_eq!(body, fixtures::GAMES_NDJSON.as_bytes());
+    assert!(games.headers.iter().all(|(name, _)| name != "authorization"));
+
+    assert_eq!(cassette.interactions[1].status, 429);
+    assert!(!std::fs::read_to_string(&path).unwrap().contains("lip_secret"));
+
+    std::fs::remove_file(&path).ok();
+}
+
+#[tokio::test]
+async fn record_split_characters() {
+    let path = cassette_path("split");
+    let server = MockServer::start().await.unwrap();
+    // The "é" of "Café" is split between the two chunks.
+    server.route(
+        Method::GET,
+        "/api/games/export/imports",
+        MockResponse::status(hyper::StatusCode::OK)
+            .with_chunk(Duration::ZERO, &b"[Event \"Caf\xc3"[..])
+            .with_chunk(Duration::from_millis(20), &b"\xa9\"]\n\n1. e4 *\n\n\n"[..]),
+    );
+
+    let mut client = server.client_with_token("lip_secret");
+    client.record_cassette(&path).unwrap();
+    let games: Vec<String> = client.export_imported_games().await.unwrap().map(Result::unwrap).collect().await;
+    assert_eq!(games, ["[Event \"Café\"]\n\n1. e4 *"]);
+    client.flush_cassette().await.unwrap();
+
+    let cassette = Cassette::load(&path).unwrap();
+    let body: Vec<u8> = cassette.interactions[0].chunks.iter().flat_map(|chunk| chunk.data.clone()).collect();
+    assert_eq!(body, "[Event \"Café\"]\n\n1. e4 *\n\n\n".as_bytes());
+
+    let mut client = Client::with_token("lip_secret");
+    client.set_lichess_host(&server.url());
+    client.replay_cassette(&path, false).unwrap();
+    let games: Vec<String> = client.export_imported_games().await.unwrap().map(Result::unwrap).collect().await;
+    assert_eq!(games, ["[Event \"Café\"]\n\n1. e4 *"]);
+
+    std::fs::remove_file(&path).ok();
+}
+
+#[tokio::test]
+async fn record_aborted_body() {
+    let path = cassette_path("aborted");
+    let server = MockServer::start().await.unwrap();
+    let first_game = fixtures::GAMES_NDJSON.lines().next().unwrap();
+    server.route(
+        Method::GET,
+        "/api/games/user/georges",
+        MockResponse::ndjson(first_game, Duration::ZERO).with_abort(Duration::from_millis(50)),
+    );
+
+    let mut client = server.client();
+    client.record_cassette(&path).unwrap();
+    let results: Vec<bool> = client
+        .export_user_games("georges", &GameExportOptions::default())
+        .await
+        .unwrap()
+        .map(|result| result.is_ok())
+        .collect()
+        .await;
+    assert_eq!(results, [true, false]);
+    client.flush_cassette().await.unwrap();
+
+    let cassette = Cassette::load(&path).unwrap();
+    assert!(cassette.interactions[0].error.is_some());
+    assert_eq!(cassette.interactions[0].chunks[0].data, format!("{}\n", first_game).as_bytes());
+
+    // The replayed body fails the same way.
+    let client = replay_client(&server.url(), &path, false);
+    let results: Vec<bool> = client
+        .export_user_games("georges", &GameExportOptions::default())
+        .await
+        .unwrap()
+        .map(|result| result.is_ok())
+        .collect()
+        .await;
+    assert_eq!(results, [true, false]);
+
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn binary_round_trip() {
+    let path = cassette_path("binary");
+    let cassette = Cassette {
+        interactions: vec![Interaction {
+            method: "POST".to_string(),
+            uri: "https://lichess.org/api/import".to_string(),
+            request_body: vec![0xff, 0x00, 0xfe],
+            status: 200,
+            headers: Vec::new(),
+            chunks: vec![
+                Chunk { delay_ms: 0, data: b"text".to_vec() },
+                Chunk { delay_ms: 5, data: vec![0x80, b'a', 0xc3] },
+            ],
+            error: None,
+        }],
+    };
+    cassette.save(&path).unwrap();
+
+    let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(json["interactions"][0]["requestBody"], serde_json::json!([255, 0, 254]));
+    assert_eq!(json["interactions"][0]["chunks"][0]["data"], "text");
+    assert_eq!(json["interactions"][0]["chunks"][1]["data"], serde_json::json!([128, 97, 195]));
+
+    let loaded = Cassette::load(&path).unwrap();
+    assert_eq!(loaded.interactions[0].request_body, cassette.interactions[0].request_body);
+    assert_eq!(loaded.interactions[0].chunks[1].data, cassette.interactions[0].chunks[1].data);
+
+    std::fs::remove_file(&path).ok();
+}
+
+#[tokio::test]
+async fn replay() {
+    let path = cassette_path("replay");
+    let url = record_fixtures(&path).await;
+    tokio::time::pause();
+
+    // The server is gone, so every response must come from the cassette.
+    let client = replay_client(&url, &path, false);
+    let games = export_games(&client).await;
+    assert_eq!(games[0].id, "yqfLYJ5E");
+    assert_eq!(games[1].id, "3bFxPQbB");
+
+    // Each interaction is only played once.
+    match client.export_user_games("georges", &GameExportOptions::default()).await {
+        Err(err) => assert!(matches!(err.downcast_ref(), Some(ClientError::NotRecorded(_, _)))),
+        Ok(_) => panic!("interaction was played twice"),
+    }
+
+    let err = client.get_user_statuses(&["georges"], false, false).await.unwrap_err();
+    assert!(matches!(err.downcast_ref(), Some(ClientError::RateLimited(_))));
+
+    std::fs::remove_file(&path).ok();
+}
+
+#[tokio::test]
+async fn replay_delays() {
+    let path = cassette_path("delays");
+    let url = record_fixtures(&path).await;
+
+    // Space the games 100ms apart.
+    let mut cassette = Cassette::load(&path).unwrap();
+    let body: Vec<u8> = cassette.interactions[0].chunks.iter().flat_map(|chunk| chunk.data.clone()).collect();
+    cassette.interactions[0].chunks = body
+        .split_inclusive(|&byte| byte == b'\n')
+        .map(|line| Chunk { delay_ms: 100, data: line.to_vec() })
+        .collect();
+    cassette.save(&path).unwrap();
+
+    // With time paused, the clock only moves when every task is waiting on a
+    // timer, so the delays are observed exactly, except that timers round
+    // their deadlines up by a millisecond.
+    tokio::time::pause();
+
+    let client = replay_client(&url, &path, false);
+    let start = Instant::now();
+    assert_eq!(export_games(&client).await.len(), 2);
+    assert_eq!(start.elapsed(), Duration::ZERO);
+
+    let client = replay_client(&url, &path, true);
+    let recorded_delay = |elapsed: Duration| (100..=101).contains(&elapsed.as_millis());
+    let start = Instant::now();
+    let mut games = client.export_user_games("georges", &GameExportOptions::default()).await.unwrap();
+    assert_eq!(games.next().await.unwrap().unwrap().id, "yqfLYJ5E");
+    assert!(recorded_delay(start.elapsed()));
+    let start = Instant::now();
+    assert_eq!(games.next().await.unwrap().unwrap().id, "3bFxPQbB");
+    assert!(recorded_delay(start.elapsed()));
+    assert!(games.next().await.is_none());
+
+    std::fs::remove_file(&path).ok();
+}
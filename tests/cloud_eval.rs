@@ -0,0 +1,90 @@
+use rust_lichess_wrapper::core::api::cloud_eval::CloudEvalCache;
+use rust_lichess_wrapper::core::mock::{MockResponse, MockServer};
+use rust_lichess_wrapper::lichess::variant::Variant;
+
+use hyper::{Method, StatusCode};
+
+use std::time::Duration;
+
+const START: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+const UNKNOWN: &str = "8/8/8/8/8/8/1k6/K7 w - - 0 1";
+
+fn eval() -> MockResponse {
+    MockResponse::json(
+        &serde_json::json!({
+            "fen": START,
+            "knodes": 2048,
+            "depth": 40,
+            "pvs": [{ "moves": "e2e4 e7e5 g1f3", "cp": 18 }],
+        })
+        .to_string(),
+    )
+}
+
+/// Move the clock forward without leaving it paused, as requests to the mock
+/// server would otherwise let the paused clock skip ahead to other timers.
+async fn advance(duration: Duration) {
+    tokio::time::pause();
+    tokio::time::advance(duration).await;
+    tokio::time::resume();
+}
+
+#[tokio::test]
+async fn cache() {
+    let server = MockServer::start().await.unwrap();
+    server.route(Method::GET, "/api/cloud-eval", eval());
+    let client = server.client();
+    let cache = CloudEvalCache::new(&client, Duration::from_secs(60));
+
+    let eval = cache.get(START, 1, None).await.unwrap().unwrap();
+    assert_eq!(eval.best().unwrap().cp, Some(18));
+    assert!(cache.get(START, 1, None).await.unwrap().is_some());
+    assert!(cache.get(&format!(" {} ", START), 1, None).await.unwrap().is_some());
+    assert_eq!(server.requests().len(), 1);
+
+    // The number of lines and the variant are part of the key.
+    cache.get(START, 3, None).await.unwrap();
+    cache.get(START, 1, Some(Variant::Chess960)).await.unwrap();
+    cache.get(START, 1, Some(Variant::Chess960)).await.unwrap();
+    let queries: Vec<String> = server.requests().into_iter().map(|request| request.query.unwrap()).collect();
+    assert_eq!(queries.len(), 3);
+    assert!(queries[1].ends_with("&multiPv=3"));
+    assert!(queries[2].ends_with("&multiPv=1&variant=chess960"));
+}
+
+#[tokio::test]
+async fn cache_missing() {
+    let server = MockServer::start().await.unwrap();
+    server.route(Method::GET, "/api/cloud-eval", MockResponse::status(StatusCode::NOT_FOUND));
+    let client = server.client();
+    let cache = CloudEvalCache::new(&client, Duration::from_secs(60));
+
+    assert!(cache.get(UNKNOWN, 1, None).await.unwrap().is_none());
+    assert!(cache.get(UNKNOWN, 1, None).await.unwrap().is_none());
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn cache_expiry() {
+    let server = MockServer::start().await.unwrap();
+    server.route(Method::GET, "/api/cloud-eval", eval());
+    let client = server.client();
+    let cache = CloudEvalCache::new(&client, Duration::from_secs(60));
+
+    cache.get(START, 1, None).await.unwrap();
+    advance(Duration::from_secs(59)).await;
+    cache.get(START, 1, None).await.unwrap();
+    assert_eq!(server.requests().len(), 1);
+
+    advance(Duration::from_secs(2)).await;
+    cache.get(START, 1, None).await.unwrap();
+    assert_eq!(server.requests().len(), 2);
+
+    // Evicted entries are fetched again.
+    cache.get(START, 3, None).await.unwrap();
+    advance(Duration::from_secs(61)).await;
+    cache.evict_expired();
+    cache.get(START, 1, None).await.unwrap();
+    cache.get(START, 3, None).await.unwrap();
+    assert_eq!(server.requests().len(), 5);
+}
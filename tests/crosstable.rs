@@ -0,0 +1,66 @@
+use rust_lichess_wrapper::lichess::crosstable::{Crosstable, HeadToHead};
+use rust_lichess_wrapper::lichess::game::Game;
+use rust_lichess_wrapper::lichess::key::Key;
+use rust_lichess_wrapper::lichess::perf_type::PerfType;
+
+const CROSSTABLE: &str = r#"{
+    "users": { "georges": 5.5, "mary": 3.5 },
+    "nbGames": 9,
+    "matchup": { "users": { "georges": 1, "mary": 0 }, "nbGames": 1 }
+}"#;
+
+fn game(id: &str, perf: &str, status: &str, winner: Option<&str>, white_diff: i32) -> Game {
+    let winner = winner.map(|winner| format!(r#""winner": "{}","#, winner)).unwrap_or_default();
+    serde_json::from_str(&format!(
+        r#"{{
+            "id": "{id}", "rated": true, "variant": "standard", "speed": "{perf}", "perf": "{perf}",
+            "createdAt": 1514505150384, "lastMoveAt": 1514505592843, "status": "{status}", {winner}
+            "players": {{
+                "white": {{ "user": {{ "name": "Georges", "id": "georges" }}, "rating": 1609, "ratingDiff": {white_diff} }},
+                "black": {{ "user": {{ "name": "Mary", "id": "mary" }}, "rating": 1545, "ratingDiff": {black_diff} }}
+            }}
+        }}"#,
+        black_diff = -white_diff,
+    ))
+    .unwrap()
+}
+
+#[test]
+fn crosstable() {
+    let crosstable: Crosstable = serde_json::from_str(CROSSTABLE).unwrap();
+    assert_eq!(crosstable.users["georges"], 5.5);
+    assert_eq!(crosstable.nb_games, 9);
+    assert_eq!(crosstable.matchup.unwrap().users["mary"], 0.0);
+
+    let without_matchup: Crosstable = serde_json::from_str(r#"{"users":{"a":0,"b":0},"nbGames":0}"#).unwrap();
+    assert!(without_matchup.matchup.is_none());
+}
+
+#[test]
+fn head_to_head() {
+    let mut head_to_head = HeadToHead::new(serde_json::from_str(CROSSTABLE).unwrap());
+    head_to_head.record("georges", &game("a", "blitz", "mate", Some("white"), 8));
+    head_to_head.record("georges", &game("b", "blitz", "resign", Some("black"), -7));
+    head_to_head.record("georges", &game("c", "blitz", "draw", None, 1));
+    head_to_head.record("georges", &game("d", "rapid", "outoftime", Some("white"), 6));
+    // Games without a result, and games the user did not play, are ignored.
+    head_to_head.record("georges", &game("e", "blitz", "aborted", None, 0));
+    head_to_head.record("georges", &game("f", "blitz", "started", None, 0));
+    head_to_head.record("thibault", &game("g", "blitz", "mate", Some("white"), 8));
+
+    let blitz = head_to_head.perfs[&Key::from(PerfType::Blitz)];
+    assert_eq!((blitz.wins, blitz.losses, blitz.draws), (1, 1, 1));
+    assert_eq!(blitz.rating_diff, 2);
+    assert_eq!(blitz.score(), 1.5);
+
+    // The same games from the opponent's side.
+    let mut opponent = HeadToHead::new(serde_json::from_str(CROSSTABLE).unwrap());
+    opponent.record("mary", &game("a", "blitz", "mate", Some("white"), 8));
+    assert_eq!(opponent.perfs[&Key::from(PerfType::Blitz)].losses, 1);
+    assert_eq!(opponent.perfs[&Key::from(PerfType::Blitz)].rating_diff, -8);
+
+    let total = head_to_head.total();
+    assert_eq!(total.games(), 4);
+    assert_eq!(total.rating_diff, 8);
+    assert!(!head_to_head.perfs.contains_key(&Key::from(PerfType::Bullet)));
+}
@@ -0,0 +1,184 @@
+use rust_lichess_wrapper::core::client::ClientError;
+use rust_lichess_wrapper::core::engine_provider::{EngineProvider, UciEngine};
+use rust_lichess_wrapper::core::mock::{MockResponse, MockServer};
+use rust_lichess_wrapper::lichess::external_engine::EngineWork;
+use rust_lichess_wrapper::lichess::variant::Variant;
+
+use hyper::{Method, StatusCode};
+
+use std::path::PathBuf;
+
+const FAKE_ENGINE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fake_engine.sh");
+
+fn engine_log(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("lichess-fake-engine-{}-{}.log", name, std::process::id()))
+}
+
+fn work(id: &str, session_id: &str, moves: &[&str]) -> String {
+    work_in_variant(id, session_id, "chess", moves)
+}
+
+fn work_in_variant(id: &str, session_id: &str, variant: &str, moves: &[&str]) -> String {
+    serde_json::json!({
+        "id": id,
+        "work": {
+            "sessionId": session_id,
+            "threads": 2,
+            "hash": 64,
+            "multiPv": 1,
+            "variant": variant,
+            "initialFen": "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "moves": moves,
+        },
+        "engine": {
+            "id": "eei_fake",
+            "name": "Fake Engine",
+            "clientSecret": "ees_client",
+            "userId": "georges",
+            "maxThreads": 4,
+            "maxHash": 256,
+            "defaultDepth": 7,
+            "variants": ["chess"],
+        },
+    })
+    .to_string()
+}
+
+#[test]
+fn work_variant() {
+    let work: EngineWork = serde_json::from_str(&work("w1", "s1", &[])).unwrap();
+    assert_eq!(work.work.variant, Variant::Standard);
+
+    let work: EngineWork = serde_json::from_str(&work_in_variant("w1", "s1", "3check", &[])).unwrap();
+    assert_eq!(work.work.variant, Variant::ThreeCheck);
+    assert_eq!(serde_json::to_value(&work).unwrap()["work"]["variant"], "3check");
+
+    assert!(serde_json::from_str::<EngineWork>(&work_in_variant("w1", "s1", "threeCheck", &[])).is_err());
+}
+
+#[tokio::test]
+async fn uci_engine() {
+    let mut engine = UciEngine::spawn("sh", [FAKE_ENGINE]).await.unwrap();
+    engine.is_ready().await.unwrap();
+    engine.send("go depth 2").await.unwrap();
+
+    let mut lines = Vec::new();
+    while let Some(line) = engine.read_line().await.unwrap() {
+        let done = line.starts_with("bestmove");
+        lines.push(line);
+        if done {
+            break;
+        }
+    }
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[2], "bestmove e2e4 ponder e7e5");
+}
+
+#[tokio::test]
+async fn work_loop() {
+    let log = engine_log("work_loop");
+    std::fs::remove_file(&log).ok();
+
+    let server = MockServer::start().await.unwrap();
+    // Once both units of work are handed out, acquiring fails, which ends
+    // the otherwise endless loop.
+    server.route(
+        Method::POST,
+        "/api/external-engine/work",
+        MockResponse::status(StatusCode::INTERNAL_SERVER_ERROR),
+    );
+    server.route_once(Method::POST, "/api/external-engine/work", MockResponse::json(&work("w2", "s1", &["e2e4"])));
+    server.route_once(Method::POST, "/api/external-engine/work", MockResponse::json(&work("w1", "s1", &[])));
+    server.route(Method::POST, "/api/external-engine/work/w1", MockResponse::status(StatusCode::OK));
+    server.route(Method::POST, "/api/external-engine/work/w2", MockResponse::status(StatusCode::OK));
+    let client = server.client();
+
+    let engine = UciEngine::spawn("sh", [FAKE_ENGINE, log.to_str().unwrap()]).await.unwrap();
+    let mut provider = EngineProvider::new(&client, "eps_secret", engine);
+    let err = provider.run().await.unwrap_err();
+    assert!(matches!(
+        err.downcast_ref(),
+        Some(ClientError::UnexpectedStatus(StatusCode::INTERNAL_SERVER_ERROR))
+    ));
+
+    let requests = server.requests();
+    let paths: Vec<&str> = requests.iter().map(|request| request.path.as_str()).collect();
+    assert_eq!(
+        paths,
+        [
+            "/api/external-engine/work",
+            "/api/external-engine/work/w1",
+            "/api/external-engine/work",
+            "/api/external-engine/work/w2",
+            "/api/external-engine/work",
+        ]
+    );
+    assert_eq!(requests[0].body, r#"{"providerSecret":"eps_secret"}"#);
+    assert_eq!(
+        requests[1].body,
+        "info depth 1 score cp 20 pv e2e4\ninfo depth 2 score cp 25 pv e2e4 e7e5\nbestmove e2e4 ponder e7e5\n"
+    );
+
+    // The engine is only reset once for both units of work, which share a
+    // session, and searches to the engine's default depth.
+    let commands = std::fs::read_to_string(&log).unwrap();
+    assert_eq!(commands.matches("ucinewgame").count(), 1);
+    assert!(!commands.contains("UCI_Variant"));
+    assert!(commands.contains("setoption name Threads value 2\n"));
+    assert!(commands.contains("position fen rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1\n"));
+    assert!(commands.contains(" w KQkq - 0 1 moves e2e4\n"));
+    assert_eq!(commands.matches("go depth 7").count(), 2);
+
+    std::fs::remove_file(&log).ok();
+}
+
+#[tokio::test]
+async fn variant_reset() {
+    let log = engine_log("variant_reset");
+    std::fs::remove_file(&log).ok();
+
+    let server = MockServer::start().await.unwrap();
+    server.route(
+        Method::POST,
+        "/api/external-engine/work",
+        MockResponse::status(StatusCode::INTERNAL_SERVER_ERROR),
+    );
+    server.route_once(Method::POST, "/api/external-engine/work", MockResponse::json(&work("w3", "s1", &[])));
+    server.route_once(
+        Method::POST,
+        "/api/external-engine/work",
+        MockResponse::json(&work_in_variant("w2", "s1", "kingofthehill", &[])),
+    );
+    server.route_once(
+        Method::POST,
+        "/api/external-engine/work",
+        MockResponse::json(&work_in_variant("w1", "s1", "kingofthehill", &[])),
+    );
+    for id in ["w1", "w2", "w3"] {
+        server.route(
+            Method::POST,
+            &format!("/api/external-engine/work/{}", id),
+            MockResponse::status(StatusCode::OK),
+        );
+    }
+    let client = server.client();
+
+    let engine = UciEngine::spawn("sh", [FAKE_ENGINE, log.to_str().unwrap()]).await.unwrap();
+    let mut provider = EngineProvider::new(&client, "eps_secret", engine);
+    provider.run().await.unwrap_err();
+
+    // The variant is set once for both King of the Hill units of work, and
+    // reset for the standard one which follows them.
+    let commands = std::fs::read_to_string(&log).unwrap();
+    let variants: Vec<&str> = commands.lines().filter(|line| line.contains("UCI_Variant")).collect();
+    assert_eq!(
+        variants,
+        [
+            "setoption name UCI_Variant value kingofthehill",
+            "setoption name UCI_Variant value chess",
+        ]
+    );
+    assert_eq!(commands.matches("go depth 7").count(), 3);
+
+    std::fs::remove_file(&log).ok();
+}
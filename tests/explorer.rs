@@ -0,0 +1,50 @@
+use rust_lichess_wrapper::core::api::explorer::ExplorerOptions;
+use rust_lichess_wrapper::core::client::{Client, ClientError};
+use rust_lichess_wrapper::core::mock::{MockResponse, MockServer};
+use rust_lichess_wrapper::lichess::game::Color;
+
+use futures::StreamExt;
+use hyper::Method;
+
+use std::time::Duration;
+
+const RESULT: &str = r#"{"white":10,"draws":2,"black":5,"moves":[]}"#;
+
+#[tokio::test]
+async fn player_is_encoded() {
+    let server = MockServer::start().await.unwrap();
+    server.route(Method::GET, "/player", MockResponse::ndjson(RESULT, Duration::ZERO));
+
+    let results: Vec<_> = server
+        .client()
+        .explore_player("Mary Jane", Color::White, &ExplorerOptions::default())
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    assert_eq!(results[0].as_ref().unwrap().total(), 17);
+
+    let query = server.requests()[0].query.clone().unwrap();
+    assert!(query.starts_with("player=Mary%20Jane&color=white&"), "{}", query);
+}
+
+#[tokio::test]
+async fn rate_limits_are_per_host() {
+    let lichess = MockServer::with_fixtures().await.unwrap();
+    let explorer = MockServer::start().await.unwrap();
+    explorer.route(Method::GET, "/lichess", MockResponse::rate_limited());
+
+    let mut client = Client::new();
+    client.set_lichess_host(&lichess.url());
+    client.set_explorer_host(&explorer.url());
+
+    let err = client.explore_lichess(&ExplorerOptions::default()).await.unwrap_err();
+    assert!(matches!(err.downcast_ref(), Some(ClientError::RateLimited(_))));
+    let err = client.explore_masters(&ExplorerOptions::default()).await.unwrap_err();
+    assert!(matches!(err.downcast_ref(), Some(ClientError::RateLimited(_))));
+    assert_eq!(explorer.requests().len(), 1);
+
+    // The main API is unaffected by the opening explorer's rate limit.
+    let statuses = client.get_user_statuses(&["georges"], false, false).await.unwrap();
+    assert_eq!(statuses.len(), 2);
+}
@@ -0,0 +1,34 @@
+#!/bin/sh
+# A stand-in for a UCI engine, answering just enough of the protocol for the
+# engine provider to drive it. Every command received is appended to the file
+# named by the first argument, if any.
+
+log=${1:-/dev/null}
+
+while read -r command; do
+    echo "$command" >> "$log"
+    case "$command" in
+        uci)
+            echo "id name Fake Engine"
+            echo "option name UCI_Variant type combo default chess var chess var kingofthehill"
+            echo "uciok"
+            ;;
+        isready)
+            echo "readyok"
+            ;;
+        "go infinite")
+            echo "info depth 1 score cp 20 pv e2e4"
+            ;;
+        go*)
+            echo "info depth 1 score cp 20 pv e2e4"
+            echo "info depth 2 score cp 25 pv e2e4 e7e5"
+            echo "bestmove e2e4 ponder e7e5"
+            ;;
+        stop)
+            echo "bestmove e2e4"
+            ;;
+        quit)
+            exit 0
+            ;;
+    esac
+done
@@ -0,0 +1,310 @@
+use rust_lichess_wrapper::core::api::games::GameExportOptions;
+use rust_lichess_wrapper::core::client::ClientError;
+use rust_lichess_wrapper::core::mock::{fixtures, MockRequest, MockResponse, MockServer};
+use rust_lichess_wrapper::lichess::game::{Color, Game};
+use rust_lichess_wrapper::lichess::key::Key;
+use rust_lichess_wrapper::lichess::perf_type::PerfType;
+use rust_lichess_wrapper::lichess::speed::Speed;
+use rust_lichess_wrapper::lichess::variant::Variant;
+
+use futures::StreamExt;
+use hyper::header::AUTHORIZATION;
+use hyper::{Method, StatusCode};
+
+use std::time::Duration;
+
+const NOW_PLAYING: &str = r#"{
+  "nowPlaying": [
+    {
+      "gameId": "yqfLYJ5E",
+      "fullId": "yqfLYJ5Ea8Kd",
+      "color": "white",
+      "fen": "rnbqkbnr/pppp1ppp/4p3/8/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2",
+      "hasMoved": true,
+      "isMyTurn": true,
+      "lastMove": "e7e6",
+      "opponent": { "id": "mary", "username": "Mary", "rating": 1545 },
+      "perf": "blitz",
+      "rated": true,
+      "secondsLeft": 287,
+      "source": "lobby",
+      "speed": "blitz",
+      "variant": { "key": "standard", "name": "Standard" }
+    },
+    {
+      "gameId": "3bFxPQbB",
+      "fullId": "3bFxPQbBx2Lm",
+      "color": "black",
+      "fen": "rnbqkbnr/pppppppp/8/8/8/5N2/PPPPPPPP/RNBQKB1R b KQkq - 1 1",
+      "hasMoved": false,
+      "isMyTurn": false,
+      "opponent": { "username": "A.I. level 3", "ai": 3 },
+      "perf": "correspondence",
+      "rated": false,
+      "source": "ai",
+      "speed": "correspondence",
+      "variant": { "key": "atomic", "name": "Atomic" }
+    }
+  ]
+}"#;
+
+fn body(request: &MockRequest) -> String {
+    String::from_utf8(request.body.to_vec()).unwrap()
+}
+
+// The clock starts paused, so that waiting out the rate limit and the interval
+// between imports takes no time.
+#[tokio::test(start_paused = true)]
+async fn import_games() {
+    let server = MockServer::start().await.unwrap();
+    server.route(
+        Method::POST,
+        "/api/import",
+        MockResponse::json(r#"{"id":"R6iLjwz5","url":"https://lichess.org/R6iLjwz5"}"#),
+    );
+    // Routes added later are served first, so the first game is rate limited,
+    // and then rejected once the rate limit is lifted.
+    server.route_once(
+        Method::POST,
+        "/api/import",
+        MockResponse::error(StatusCode::BAD_REQUEST, "Invalid PGN"),
+    );
+    server.route_once(Method::POST, "/api/import", MockResponse::rate_limited());
+    let client = server.client();
+
+    let pgn = "[White \"Georges\"]\n\n1. e4 *\n\n[White \"Mary\"]\n\n1. d4 *\n\n[White \"Jane\"]\n\n1. c4 *\n";
+    let results = client.import_games(pgn, Duration::from_secs(1)).await;
+
+    assert_eq!(results.len(), 3);
+    match results[0].as_ref().unwrap_err().downcast_ref::<ClientError>() {
+        Some(ClientError::Rejected(status, message)) => {
+            assert_eq!(*status, StatusCode::BAD_REQUEST);
+            assert_eq!(message, "Invalid PGN");
+        }
+        _ => panic!("unexpected {}", results[0].as_ref().unwrap_err()),
+    }
+    assert_eq!(results[1].as_ref().unwrap().id, "R6iLjwz5");
+    assert_eq!(results[2].as_ref().unwrap().url, "https://lichess.org/R6iLjwz5");
+
+    // The rate limited game is retried, and the games are imported in order.
+    let bodies: Vec<String> = server.requests().iter().map(body).collect();
+    assert_eq!(bodies.len(), 4);
+    assert!(bodies[0].contains("Georges"));
+    assert_eq!(bodies[1], bodies[0]);
+    assert!(bodies[2].contains("Mary"));
+    assert!(bodies[3].contains("Jane"));
+}
+
+#[tokio::test]
+async fn ongoing_games() {
+    let server = MockServer::start().await.unwrap();
+    server.route(Method::GET, "/api/account/playing", MockResponse::json(NOW_PLAYING));
+    let client = server.client_with_token("lip_secret");
+
+    let games = client.get_ongoing_games(2).await.unwrap();
+    assert_eq!(games.len(), 2);
+    assert_eq!(games[0].full_id, "yqfLYJ5Ea8Kd");
+    assert_eq!(games[0].color, Color::White);
+    assert_eq!(games[0].last_move, "e7e6");
+    assert_eq!(games[0].opponent.id.as_deref(), Some("mary"));
+    assert_eq!(games[0].perf, PerfType::Blitz.into());
+    assert_eq!(games[0].seconds_left, Some(287));
+    assert_eq!(games[1].last_move, "");
+    assert_eq!(games[1].opponent.id, None);
+    assert_eq!(games[1].opponent.ai, Some(3));
+    assert_eq!(games[1].speed, Speed::Correspondence.into());
+    assert_eq!(games[1].variant.key, Variant::Atomic.into());
+
+    let requests = server.requests();
+    assert_eq!(requests[0].query.as_deref(), Some("nb=2"));
+    assert_eq!(requests[0].headers[AUTHORIZATION], "Bearer lip_secret");
+}
+
+#[tokio::test]
+async fn bookmarked_games() {
+    let server = MockServer::start().await.unwrap();
+    server.route(
+        Method::GET,
+        "/api/games/export/bookmarks",
+        MockResponse::ndjson(fixtures::GAMES_NDJSON, Duration::ZERO),
+    );
+    let client = server.client_with_token("lip_secret");
+
+    let options = GameExportOptions {
+        max: Some(2),
+        moves: Some(false),
+        ..GameExportOptions::default()
+    };
+    let games: Vec<_> = client
+        .export_bookmarked_games(&options)
+        .await
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+    assert_eq!(games.len(), 2);
+    assert_eq!(games[0].id, "yqfLYJ5E");
+    assert_eq!(games[1].variant, Variant::Atomic.into());
+
+    let requests = server.requests();
+    assert_eq!(requests[0].query.as_deref(), Some("max=2&moves=false"));
+    assert_eq!(requests[0].headers[AUTHORIZATION], "Bearer lip_secret");
+}
+
+#[tokio::test]
+async fn imported_games() {
+    let server = MockServer::with_fixtures().await.unwrap();
+    let client = server.client_with_token("lip_secret");
+
+    let games: Vec<String> = client
+        .export_imported_games()
+        .await
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+    assert_eq!(games.len(), 2);
+    assert!(games[0].contains("[Site \"https://lichess.org/yqfLYJ5E\"]"));
+    assert!(games[1].contains("[Variant \"Atomic\"]"));
+
+    let requests = server.requests();
+    assert_eq!(requests[0].path, "/api/games/export/imports");
+    assert_eq!(requests[0].query, None);
+}
+
+#[tokio::test]
+async fn requires_token() {
+    let server = MockServer::with_fixtures().await.unwrap();
+    let client = server.client();
+
+    assert!(client.get_ongoing_games(2).await.is_err());
+    assert!(client.export_bookmarked_games(&GameExportOptions::default()).await.is_err());
+    assert!(client.export_imported_games().await.is_err());
+    assert!(server.requests().is_empty());
+}
+
+fn ids(n: usize) -> Vec<String> {
+    (0..n).map(|i| format!("g{:07}", i)).collect()
+}
+
+// The clock starts paused, so that waiting out the rate limit takes no time.
+#[tokio::test(start_paused = true)]
+async fn export_all_by_ids() {
+    let server = MockServer::start().await.unwrap();
+    server.route(
+        Method::POST,
+        "/api/games/export/_ids",
+        MockResponse::ndjson(fixtures::GAMES_NDJSON, Duration::ZERO),
+    );
+    server.route_once(Method::POST, "/api/games/export/_ids", MockResponse::rate_limited());
+    let client = server.client();
+
+    let ids = ids(301);
+    let ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+    let options = GameExportOptions {
+        moves: Some(false),
+        ..GameExportOptions::default()
+    };
+    let games: Vec<_> = client
+        .export_all_games_by_ids(&ids, &options)
+        .map(Result::unwrap)
+        .collect()
+        .await;
+    let game_ids: Vec<&str> = games.iter().map(|game| game.id.as_str()).collect();
+    assert_eq!(game_ids, ["yqfLYJ5E", "3bFxPQbB", "yqfLYJ5E", "3bFxPQbB"]);
+
+    // The rate limited chunk is requested again, and the chunks are requested
+    // in the order of the ids.
+    let requests = server.requests();
+    let bodies: Vec<String> = requests.iter().map(body).collect();
+    assert_eq!(bodies.len(), 3);
+    assert_eq!(bodies[0], ids[..300].join(","));
+    assert_eq!(bodies[1], bodies[0]);
+    assert_eq!(bodies[2], "g0000300");
+    assert!(requests.iter().all(|request| request.query.as_deref() == Some("moves=false")));
+}
+
+#[tokio::test]
+async fn export_all_by_ids_failure() {
+    let server = MockServer::start().await.unwrap();
+    server.route(
+        Method::POST,
+        "/api/games/export/_ids",
+        MockResponse::ndjson(fixtures::GAMES_NDJSON, Duration::ZERO),
+    );
+    let client = server.client();
+
+    let ids = ids(601);
+    let ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+    let options = GameExportOptions::default();
+    let mut games = Box::pin(client.export_all_games_by_ids(&ids, &options));
+
+    // The next chunk is only requested once the games of the first chunk have
+    // been streamed, so only the middle chunk is rejected.
+    assert_eq!(games.next().await.unwrap().unwrap().id, "yqfLYJ5E");
+    assert_eq!(games.next().await.unwrap().unwrap().id, "3bFxPQbB");
+    server.route_once(
+        Method::POST,
+        "/api/games/export/_ids",
+        MockResponse::error(StatusCode::BAD_REQUEST, "Invalid ids"),
+    );
+    let e = games.next().await.unwrap().unwrap_err();
+    assert!(matches!(e.downcast_ref::<ClientError>(), Some(ClientError::Rejected(_, _))));
+    assert_eq!(games.next().await.unwrap().unwrap().id, "yqfLYJ5E");
+    assert_eq!(games.next().await.unwrap().unwrap().id, "3bFxPQbB");
+    assert!(games.next().await.is_none());
+
+    let bodies: Vec<String> = server.requests().iter().map(body).collect();
+    assert_eq!(bodies.len(), 3);
+    assert_eq!(bodies[0], ids[..300].join(","));
+    assert_eq!(bodies[1], ids[300..600].join(","));
+    assert_eq!(bodies[2], "g0000600");
+}
+
+#[tokio::test]
+async fn unknown_keys() {
+    let duck_chess = fixtures::GAMES_NDJSON
+        .lines()
+        .next()
+        .unwrap()
+        .replace("yqfLYJ5E", "Dk4cH3ss")
+        .replace(r#""variant":"standard""#, r#""variant":"duckChess""#)
+        .replace(r#""speed":"blitz""#, r#""speed":"hyperBullet""#)
+        .replace(r#""perf":"blitz""#, r#""perf":"duckChess""#);
+    let ndjson = format!("{}\n{}", duck_chess, fixtures::GAMES_NDJSON);
+
+    let server = MockServer::start().await.unwrap();
+    server.route(Method::GET, "/api/games/user/georges", MockResponse::ndjson(&ndjson, Duration::ZERO));
+    let client = server.client();
+
+    // A game of a variant unknown to the crate does not end the stream.
+    let games: Vec<Game> = client
+        .export_user_games("georges", &GameExportOptions::default())
+        .await
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+    assert_eq!(games.len(), 3);
+    assert_eq!(games[0].variant, Key::Other("duckChess".to_string()));
+    assert_eq!(games[0].speed, Key::Other("hyperBullet".to_string()));
+    assert_eq!(games[0].perf.known(), None);
+    assert_eq!(games[1].variant.known(), Some(&Variant::Standard));
+    assert_eq!(games[2].perf, PerfType::Atomic.into());
+
+    // Unknown keys are serialized as they were sent.
+    let json = serde_json::to_value(&games[0]).unwrap();
+    assert_eq!(json["variant"], "duckChess");
+    assert_eq!(json["perf"], "duckChess");
+    assert_eq!(serde_json::to_value(&games[1]).unwrap()["variant"], "standard");
+
+    let now_playing = NOW_PLAYING
+        .replace(r#""key": "atomic""#, r#""key": "duckChess""#)
+        .replace(r#""perf": "correspondence""#, r#""perf": "duckChess""#);
+    server.route(Method::GET, "/api/account/playing", MockResponse::json(&now_playing));
+    let client = server.client_with_token("lip_secret");
+    let ongoing = client.get_ongoing_games(2).await.unwrap();
+    assert_eq!(ongoing[1].variant.key, Key::Other("duckChess".to_string()));
+    assert_eq!(ongoing[1].perf, Key::Other("duckChess".to_string()));
+    assert_eq!(ongoing[1].speed, Speed::Correspondence.into());
+}
@@ -0,0 +1,71 @@
+use rust_lichess_wrapper::core::api::inbox::InboxError;
+use rust_lichess_wrapper::core::mock::{MockResponse, MockServer};
+
+use hyper::{Method, StatusCode};
+
+use std::time::{Duration, Instant};
+
+#[tokio::test]
+async fn send() {
+    let server = MockServer::start().await.unwrap();
+    server.route(Method::POST, "/inbox/georges", MockResponse::json(r#"{"ok":true}"#));
+    let client = server.client_with_token("lip_secret");
+
+    client.send_message("georges", "Good game!").await.unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests[0].path, "/inbox/georges");
+    assert_eq!(requests[0].body, "text=Good%20game%21");
+    assert_eq!(requests[0].headers["authorization"], "Bearer lip_secret");
+}
+
+#[tokio::test]
+async fn rate_limited() {
+    let server = MockServer::start().await.unwrap();
+    server.route(Method::POST, "/inbox/georges", MockResponse::rate_limited());
+    let client = server.client_with_token("lip_secret");
+
+    let before = Instant::now();
+    let e = client.send_message("georges", "Good game!").await.unwrap_err();
+    let first = match e.downcast_ref::<InboxError>() {
+        Some(InboxError::RateLimited(Some(until))) => *until,
+        _ => panic!("unexpected {}", e),
+    };
+    assert!(first >= before + Duration::from_secs(60));
+
+    // The client keeps refusing requests until the rate limit is lifted,
+    // without contacting Lichess again.
+    let e = client.send_message("mary", "Good game!").await.unwrap_err();
+    match e.downcast_ref::<InboxError>() {
+        Some(InboxError::RateLimited(until)) => assert_eq!(*until, Some(first)),
+        _ => panic!("unexpected {}", e),
+    }
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn refused() {
+    let server = MockServer::start().await.unwrap();
+    server.route(
+        Method::POST,
+        "/inbox/georges",
+        MockResponse::error(StatusCode::BAD_REQUEST, "Too many messages. Try again later."),
+    );
+    server.route(
+        Method::POST,
+        "/inbox/mary",
+        MockResponse::error(StatusCode::FORBIDDEN, "This user doesn't accept new messages"),
+    );
+    let client = server.client_with_token("lip_secret");
+
+    let e = client.send_message("georges", "Good game!").await.unwrap_err();
+    assert!(matches!(e.downcast_ref::<InboxError>(), Some(InboxError::RateLimited(None))));
+
+    let e = client.send_message("mary", "Good game!").await.unwrap_err();
+    match e.downcast_ref::<InboxError>() {
+        Some(InboxError::Blocked(message)) => {
+            assert_eq!(message, "This user doesn't accept new messages")
+        }
+        _ => panic!("unexpected {}", e),
+    }
+}
@@ -0,0 +1,105 @@
+use rust_lichess_wrapper::core::api::games::GameExportOptions;
+use rust_lichess_wrapper::core::client::ClientError;
+use rust_lichess_wrapper::core::mock::{fixtures, MockResponse, MockServer};
+use rust_lichess_wrapper::lichess::game::Color;
+use rust_lichess_wrapper::lichess::title::Title;
+
+use futures::StreamExt;
+use hyper::header::AUTHORIZATION;
+use hyper::{Method, StatusCode};
+
+use std::time::Duration;
+
+#[tokio::test]
+async fn serves_fixtures() {
+    let server = MockServer::with_fixtures().await.unwrap();
+    let client = server.client();
+
+    let statuses = client.get_user_statuses(&["georges", "mary"], true, true).await.unwrap();
+    assert_eq!(statuses.len(), 2);
+    assert_eq!(statuses[0].title, Some(Title::NM));
+    assert_eq!(statuses[0].playing_id.as_deref(), Some("yqfLYJ5E"));
+    assert!(!statuses[1].online);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/api/users/status");
+    assert_eq!(
+        requests[0].query.as_deref(),
+        Some("ids=georges,mary&withSignal=true&withGameIds=true")
+    );
+}
+
+#[tokio::test]
+async fn streams_ndjson() {
+    let server = MockServer::start().await.unwrap();
+    server.route(
+        Method::GET,
+        "/api/games/user/georges",
+        MockResponse::ndjson(fixtures::GAMES_NDJSON, Duration::from_millis(50)),
+    );
+    let client = server.client();
+
+    let games: Vec<_> = client
+        .export_user_games("georges", &GameExportOptions::default())
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    assert_eq!(games.len(), 2);
+    let game = games[0].as_ref().unwrap();
+    assert_eq!(game.id, "yqfLYJ5E");
+    assert_eq!(game.color_of("georges"), Some(Color::White));
+    assert_eq!(games[1].as_ref().unwrap().winner, Some(Color::Black));
+}
+
+#[tokio::test]
+async fn streams_pgn() {
+    let server = MockServer::with_fixtures().await.unwrap();
+    let client = server.client_with_token("lip_mock");
+
+    let games: Vec<_> = client.export_imported_games().await.unwrap().collect().await;
+    assert_eq!(games.len(), 2);
+    assert!(games[1].as_ref().unwrap().contains("[Variant \"Atomic\"]"));
+
+    let requests = server.requests();
+    assert_eq!(requests[0].headers[AUTHORIZATION], "Bearer lip_mock");
+}
+
+#[tokio::test]
+async fn rate_limited() {
+    let server = MockServer::with_fixtures().await.unwrap();
+    server.route_once(Method::GET, "/api/users/status", MockResponse::rate_limited());
+    let client = server.client();
+
+    let err = client.get_user_statuses(&["georges"], false, false).await.unwrap_err();
+    assert!(matches!(err.downcast_ref(), Some(ClientError::RateLimited(_))));
+
+    // The client refuses further requests without contacting the server.
+    let err = client.get_user_statuses(&["georges"], false, false).await.unwrap_err();
+    assert!(matches!(err.downcast_ref(), Some(ClientError::RateLimited(_))));
+    assert_eq!(server.requests().len(), 1);
+
+    // Once the route is used up, a new client is served the fixture again.
+    let statuses = server.client().get_user_statuses(&["georges"], false, false).await.unwrap();
+    assert_eq!(statuses[0].id, "georges");
+}
+
+#[tokio::test]
+async fn rejected() {
+    let server = MockServer::start().await.unwrap();
+    server.route(
+        Method::GET,
+        "/api/users/status",
+        MockResponse::error(StatusCode::BAD_REQUEST, "Too many ids"),
+    );
+
+    let err = server.client().get_user_statuses(&["georges"], false, false).await.unwrap_err();
+    match err.downcast_ref() {
+        Some(ClientError::Rejected(status, message)) => {
+            assert_eq!(*status, StatusCode::BAD_REQUEST);
+            assert_eq!(message, "Too many ids");
+        }
+        other => panic!("unexpected error {:?}", other),
+    }
+}
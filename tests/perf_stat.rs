@@ -0,0 +1,67 @@
+use rust_lichess_wrapper::lichess::perf_stat::PerfStat;
+use rust_lichess_wrapper::lichess::perf_type::PerfType;
+use rust_lichess_wrapper::lichess::title::Title;
+
+use chrono::Duration;
+
+const PERF_STAT: &str = include_str!("fixtures/perf_stat.json");
+
+#[test]
+fn perf_stat() {
+    let perf_stat: PerfStat = serde_json::from_str(PERF_STAT).unwrap();
+    assert_eq!(perf_stat.rank, Some(48102));
+    assert_eq!(perf_stat.percentile, Some(61.4));
+    assert_eq!(perf_stat.perf.glicko.rating, 1624.33);
+    assert!(!perf_stat.perf.glicko.provisional);
+
+    let stat = &perf_stat.stat;
+    assert_eq!(stat.perf_type.key, PerfType::Blitz);
+    let highest = stat.highest.as_ref().unwrap();
+    assert_eq!(highest.rating, 1703);
+    assert_eq!(highest.game_id, "Qz4UuS3b");
+    assert_eq!(highest.at.to_rfc3339(), "2021-11-06T19:41:08.214+00:00");
+    assert_eq!(stat.lowest.as_ref().unwrap().rating, 1342);
+
+    let best = &stat.best_wins.results;
+    assert_eq!(best.len(), 2);
+    assert_eq!(best[0].opponent.id, "mary");
+    assert_eq!(best[0].opponent.title, Some(Title::WFM));
+    assert_eq!(best[1].opponent.title, None);
+    assert!(stat.worst_losses.results.is_empty());
+
+    assert_eq!(stat.count.op_avg.pop, 1187);
+    assert_eq!(stat.count.time_played(), Duration::seconds(539820));
+
+    // Empty streaks have no bounds.
+    let wins = &stat.result_streak.win;
+    assert_eq!(wins.cur.v, 0);
+    assert!(wins.cur.from.is_none() && wins.cur.to.is_none());
+    assert_eq!(wins.max.v, 9);
+    assert_eq!(wins.max.to.as_ref().unwrap().game_id, "Rt7nVbXc");
+    assert_eq!(stat.result_streak.loss.cur.from.as_ref().unwrap().game_id, "Ab12Cd34");
+    assert_eq!(stat.play_streak.time.max.v, 12600);
+    assert!(stat.play_streak.last_date.is_some());
+}
+
+#[test]
+fn unranked() {
+    // Users with a provisional rating have no rank, percentile, or lowest
+    // rating yet.
+    let mut json: serde_json::Value = serde_json::from_str(PERF_STAT).unwrap();
+    json["perf"]["glicko"]["provisional"] = serde_json::Value::Bool(true);
+    for field in ["rank", "percentile"] {
+        json.as_object_mut().unwrap().remove(field);
+    }
+    for field in ["highest", "lowest"] {
+        json["stat"].as_object_mut().unwrap().remove(field);
+    }
+    json["stat"]["playStreak"].as_object_mut().unwrap().remove("lastDate");
+
+    let perf_stat: PerfStat = serde_json::from_value(json).unwrap();
+    assert!(perf_stat.perf.glicko.provisional);
+    assert_eq!(perf_stat.rank, None);
+    assert_eq!(perf_stat.percentile, None);
+    assert!(perf_stat.stat.highest.is_none());
+    assert!(perf_stat.stat.lowest.is_none());
+    assert!(perf_stat.stat.play_streak.last_date.is_none());
+}
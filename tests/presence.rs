@@ -0,0 +1,75 @@
+use rust_lichess_wrapper::core::mock::{MockResponse, MockServer};
+use rust_lichess_wrapper::core::presence::{PresenceEvent, PresenceWatcher};
+
+use hyper::Method;
+
+use std::time::Duration;
+
+fn statuses(georges: (bool, bool), mary: (bool, bool)) -> MockResponse {
+    MockResponse::json(
+        &serde_json::json!([
+            { "id": "georges", "name": "Georges", "online": georges.0, "playing": georges.1 },
+            { "id": "mary", "name": "Mary", "online": mary.0, "playing": mary.1 },
+        ])
+        .to_string(),
+    )
+}
+
+fn describe(events: &[PresenceEvent]) -> Vec<String> {
+    events
+        .iter()
+        .map(|event| match event {
+            PresenceEvent::WentOnline(status) => format!("{} online", status.id),
+            PresenceEvent::StartedPlaying(status) => format!("{} playing", status.id),
+            PresenceEvent::WentOffline(status) => format!("{} offline", status.id),
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn detects_changes() {
+    let server = MockServer::start().await.unwrap();
+    // Later routes take precedence, so the polls are served from the last
+    // route added backwards.
+    server.route(Method::GET, "/api/users/status", statuses((false, false), (true, false)));
+    server.route_once(Method::GET, "/api/users/status", statuses((true, true), (true, false)));
+    server.route_once(Method::GET, "/api/users/status", statuses((true, false), (false, false)));
+    let client = server.client();
+
+    let mut watcher = PresenceWatcher::new(&client, Duration::ZERO);
+    watcher.track("Georges");
+    watcher.track("mary");
+    watcher.track("GEORGES");
+
+    assert_eq!(describe(&watcher.poll().await.unwrap()), ["georges online"]);
+    assert_eq!(describe(&watcher.poll().await.unwrap()), ["georges playing", "mary online"]);
+    assert_eq!(describe(&watcher.poll().await.unwrap()), ["georges offline"]);
+    assert!(watcher.poll().await.unwrap().is_empty());
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 4);
+    assert!(requests[0].query.as_deref().unwrap().starts_with("ids=georges,mary&"));
+}
+
+#[tokio::test]
+async fn batches_requests() {
+    let server = MockServer::start().await.unwrap();
+    server.route(Method::GET, "/api/users/status", MockResponse::json("[]"));
+    let client = server.client();
+
+    let mut watcher = PresenceWatcher::new(&client, Duration::ZERO);
+    for i in 0..150 {
+        watcher.track(&format!("user{}", i));
+    }
+    watcher.poll().await.unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    let ids = |i: usize| {
+        let query = requests[i].query.clone().unwrap();
+        let ids = query.split('&').next().unwrap().trim_start_matches("ids=").to_string();
+        ids.split(',').count()
+    };
+    assert_eq!(ids(0), 100);
+    assert_eq!(ids(1), 50);
+}
@@ -0,0 +1,127 @@
+use rust_lichess_wrapper::lichess::perf_type::PerfType;
+use rust_lichess_wrapper::lichess::rating_history::RatingHistory;
+
+use chrono::NaiveDate;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+// Blitz records on 2021-03-10 (1500), 2021-03-12 (1550), 2021-03-15 (1530) and
+// 2021-04-01 (1550). Months are zero-based in the API.
+fn history() -> RatingHistory {
+    serde_json::from_str(
+        r#"[
+            { "name": "Blitz", "points": [[2021, 2, 10, 1500], [2021, 2, 12, 1550], [2021, 2, 15, 1530], [2021, 3, 1, 1550]] },
+            { "name": "Bullet", "points": [] }
+        ]"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn shuffled_perfs() {
+    let history: RatingHistory = serde_json::from_str(
+        r#"[
+            { "name": "Puzzles", "points": [[2022, 0, 1, 2100]] },
+            { "name": "King of the Hill", "points": [[2022, 0, 2, 1700]] },
+            { "name": "Bullet", "points": [[2022, 0, 3, 1400]] },
+            { "name": "UltraBullet", "points": [[2022, 0, 4, 1300]] }
+        ]"#,
+    )
+    .unwrap();
+    assert_eq!(history.puzzles[0].rating, 2100);
+    assert_eq!(history.king_of_the_hill[0].rating, 1700);
+    assert_eq!(history.bullet[0].rating, 1400);
+    assert_eq!(history.get(PerfType::UltraBullet)[0].date, date(2022, 1, 4));
+}
+
+#[test]
+fn missing_perfs() {
+    let history: RatingHistory = serde_json::from_str(r#"[{ "name": "Rapid", "points": [[2022, 11, 31, 1800]] }]"#).unwrap();
+    assert_eq!(history.rapid[0].date, date(2022, 12, 31));
+    for (perf, records) in history.iter() {
+        assert_eq!(records.is_empty(), perf != PerfType::Rapid, "{}", perf);
+    }
+
+    let history: RatingHistory = serde_json::from_str("[]").unwrap();
+    assert!(history.iter().all(|(_, records)| records.is_empty()));
+}
+
+#[test]
+fn unknown_perfs() {
+    let history: RatingHistory = serde_json::from_str(
+        r#"[
+            { "name": "Duck Chess", "points": [[2022, 0, 1, 1900]] },
+            { "name": "Blitz", "points": [[2022, 0, 1, 1600]] }
+        ]"#,
+    )
+    .unwrap();
+    assert_eq!(history.blitz.len(), 1);
+    assert_eq!(history.blitz[0].rating, 1600);
+    assert_eq!(history.iter().filter(|(_, records)| !records.is_empty()).count(), 1);
+}
+
+#[test]
+fn unsorted_points() {
+    let history: RatingHistory =
+        serde_json::from_str(r#"[{ "name": "Blitz", "points": [[2021, 3, 1, 1550], [2021, 2, 10, 1500]] }]"#).unwrap();
+    assert_eq!(history.blitz[0].date, date(2021, 3, 10));
+    assert_eq!(history.blitz[1].date, date(2021, 4, 1));
+}
+
+#[test]
+fn invalid_date() {
+    let result = serde_json::from_str::<RatingHistory>(r#"[{ "name": "Blitz", "points": [[2021, 1, 30, 1500]] }]"#);
+    assert!(result.is_err());
+    let result = serde_json::from_str::<RatingHistory>(r#"[{ "name": "Blitz", "points": [[2021, -1, 1, 1500]] }]"#);
+    assert!(result.is_err());
+    let result = serde_json::from_str::<RatingHistory>(r#"[{ "name": "Blitz", "points": [[2021, 2147483647, 1, 1500]] }]"#);
+    assert!(result.is_err());
+}
+
+#[test]
+fn rating_at() {
+    let history = history();
+    let blitz = PerfType::Blitz;
+    assert_eq!(history.rating_at(blitz, date(2021, 3, 9)), None);
+    assert_eq!(history.rating_at(blitz, date(2021, 3, 10)), Some(1500));
+    assert_eq!(history.rating_at(blitz, date(2021, 3, 11)), Some(1500));
+    assert_eq!(history.rating_at(blitz, date(2021, 3, 12)), Some(1550));
+    assert_eq!(history.rating_at(blitz, date(2021, 4, 1)), Some(1550));
+    assert_eq!(history.rating_at(blitz, date(2030, 1, 1)), Some(1550));
+    assert_eq!(history.rating_at(PerfType::Bullet, date(2021, 3, 12)), None);
+}
+
+#[test]
+fn peak() {
+    let history = history();
+    // The peak of 1550 was reached twice, and the earliest is returned.
+    let peak = history.peak(PerfType::Blitz).unwrap();
+    assert_eq!(peak.rating, 1550);
+    assert_eq!(peak.date, date(2021, 3, 12));
+    assert_eq!(history.peak(PerfType::Bullet), None);
+}
+
+#[test]
+fn rating_change() {
+    let history = history();
+    let change = |from, to| history.rating_change(PerfType::Blitz, from, to);
+
+    // Both ends of the window are inclusive.
+    assert_eq!(change(date(2021, 3, 12), date(2021, 3, 15)), Some(30));
+    assert_eq!(change(date(2021, 3, 13), date(2021, 3, 15)), Some(-20));
+    assert_eq!(change(date(2021, 3, 12), date(2021, 3, 14)), Some(50));
+    assert_eq!(change(date(2021, 3, 12), date(2021, 3, 12)), Some(50));
+    assert_eq!(change(date(2021, 3, 13), date(2021, 3, 14)), Some(0));
+
+    // Without a rating before the window, the change is measured from the
+    // first rating in it.
+    assert_eq!(change(date(2021, 1, 1), date(2021, 3, 15)), Some(30));
+    assert_eq!(change(date(2021, 1, 1), date(2021, 3, 10)), Some(0));
+
+    // Windows entirely before or after the records.
+    assert_eq!(change(date(2021, 1, 1), date(2021, 3, 9)), None);
+    assert_eq!(change(date(2021, 5, 1), date(2021, 6, 1)), Some(0));
+    assert_eq!(history.rating_change(PerfType::Bullet, date(2021, 1, 1), date(2022, 1, 1)), None);
+}
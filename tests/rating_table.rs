@@ -0,0 +1,176 @@
+use rust_lichess_wrapper::analytics::rating_table::{RatingTable, Resolution};
+use rust_lichess_wrapper::lichess::perf_type::PerfType;
+use rust_lichess_wrapper::lichess::rating_history::RatingHistory;
+
+use chrono::NaiveDate;
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+// Bullet is first rated a day after blitz, and blitz is not rated on the
+// second day. Months are zero-based in the API.
+fn history() -> RatingHistory {
+    serde_json::from_str(
+        r#"[
+            { "name": "Bullet", "points": [[2021, 0, 2, 1400]] },
+            { "name": "Blitz", "points": [[2021, 0, 1, 1500], [2021, 0, 3, 1520], [2021, 0, 4, 1490]] }
+        ]"#,
+    )
+    .unwrap()
+}
+
+fn column<'a>(table: &'a RatingTable, name: &str) -> &'a [Option<f64>] {
+    &table.columns.iter().find(|column| column.name == name).unwrap().values
+}
+
+#[test]
+fn resample_daily() {
+    let table = RatingTable::perfs(&history(), Resolution::Daily);
+    assert_eq!(table.dates, [date(2021, 1, 1), date(2021, 1, 2), date(2021, 1, 3), date(2021, 1, 4)]);
+    let names: Vec<&str> = table.columns.iter().map(|column| column.name.as_str()).collect();
+    assert_eq!(names, ["bullet", "blitz"]);
+    assert_eq!(column(&table, "bullet"), [None, Some(1400.0), Some(1400.0), Some(1400.0)]);
+    assert_eq!(column(&table, "blitz"), [Some(1500.0), Some(1500.0), Some(1520.0), Some(1490.0)]);
+}
+
+#[test]
+fn resample_weekly() {
+    // The last row falls after the last rating, so that it reflects it.
+    let table = RatingTable::perfs(&history(), Resolution::Weekly);
+    assert_eq!(table.dates, [date(2021, 1, 1), date(2021, 1, 8)]);
+    assert_eq!(column(&table, "blitz"), [Some(1500.0), Some(1490.0)]);
+    assert_eq!(column(&table, "bullet"), [None, Some(1400.0)]);
+}
+
+#[test]
+fn compare() {
+    let history = history();
+    let empty = RatingHistory::default();
+    let table = RatingTable::compare(&[("georges", &history), ("mary", &empty)], PerfType::Bullet, Resolution::Daily);
+    assert_eq!(table.dates, [date(2021, 1, 2)]);
+    assert_eq!(column(&table, "georges"), [Some(1400.0)]);
+    assert_eq!(column(&table, "mary"), [None]);
+
+    let table = RatingTable::compare(&[("mary", &empty)], PerfType::Bullet, Resolution::Daily);
+    assert!(table.dates.is_empty());
+    assert!(table.columns.is_empty());
+}
+
+#[test]
+fn rolling_average() {
+    let mut table = RatingTable::perfs(&history(), Resolution::Daily);
+    table.add_rolling_average(2);
+    assert_eq!(column(&table, "bullet_avg2"), [None, Some(1400.0), Some(1400.0), Some(1400.0)]);
+    assert_eq!(column(&table, "blitz_avg2"), [Some(1500.0), Some(1500.0), Some(1510.0), Some(1505.0)]);
+
+    // Derived columns are not themselves averaged again.
+    table.add_rolling_average(1);
+    assert_eq!(table.columns.len(), 6);
+    assert_eq!(column(&table, "blitz_avg1"), column(&table, "blitz"));
+}
+
+#[test]
+fn rolling_average_short_window() {
+    let mut table = RatingTable::perfs(&history(), Resolution::Daily);
+    table.add_rolling_average(0);
+    assert_eq!(column(&table, "blitz_avg0"), column(&table, "blitz"));
+}
+
+#[test]
+fn volatility() {
+    let mut table = RatingTable::perfs(&history(), Resolution::Daily);
+    table.add_volatility(3);
+    // Changes of [0, 20] and then [20, -30].
+    assert_eq!(column(&table, "blitz_vol3"), [None, Some(0.0), Some(10.0), Some(25.0)]);
+    // The gap before the first bullet rating is skipped.
+    assert_eq!(column(&table, "bullet_vol3"), [None, None, Some(0.0), Some(0.0)]);
+}
+
+#[test]
+fn volatility_short_window() {
+    let mut table = RatingTable::perfs(&history(), Resolution::Daily);
+    table.add_volatility(1);
+    table.add_volatility(0);
+    assert_eq!(column(&table, "blitz_vol1"), [None; 4]);
+    assert_eq!(column(&table, "blitz_vol0"), [None; 4]);
+}
+
+#[test]
+fn csv() {
+    let mut table = RatingTable::perfs(&history(), Resolution::Daily);
+    table.add_volatility(3);
+    let mut csv = Vec::new();
+    table.write_csv(&mut csv).unwrap();
+    assert_eq!(
+        String::from_utf8(csv).unwrap(),
+        "date,bullet,blitz,bullet_vol3,blitz_vol3\n\
+         2021-01-01,,1500,,\n\
+         2021-01-02,1400,1500,,0\n\
+         2021-01-03,1400,1520,0,10\n\
+         2021-01-04,1400,1490,0,25\n"
+    );
+}
+
+#[test]
+fn json() {
+    let history = history();
+    let table = RatingTable::compare(
+        &[("Mary \"M\"", &history), ("georges", &history)],
+        PerfType::Bullet,
+        Resolution::Daily,
+    );
+    let mut json = Vec::new();
+    table.write_json(&mut json).unwrap();
+    assert_eq!(
+        String::from_utf8(json).unwrap(),
+        r#"[{"date":"2021-01-02","Mary \"M\"":1400.0,"georges":1400.0}]"#
+    );
+
+    let mut json = Vec::new();
+    RatingTable::perfs(&history, Resolution::Daily).write_json(&mut json).unwrap();
+    let rows: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(rows[0]["bullet"], serde_json::Value::Null);
+    assert_eq!(rows[3]["blitz"], 1490.0);
+
+    let mut json = Vec::new();
+    RatingTable::default().write_json(&mut json).unwrap();
+    assert_eq!(json, b"[]");
+}
+
+#[test]
+fn repeated_names() {
+    let history = history();
+    let mut table = RatingTable::compare(
+        &[("georges", &history), ("georges", &history), ("georges_avg2", &history)],
+        PerfType::Bullet,
+        Resolution::Daily,
+    );
+    table.add_rolling_average(2);
+    let names: Vec<&str> = table.columns.iter().map(|column| column.name.as_str()).collect();
+    assert_eq!(
+        names,
+        ["georges", "georges_2", "georges_avg2", "georges_avg2_2", "georges_2_avg2", "georges_avg2_avg2"]
+    );
+
+    let mut json = Vec::new();
+    table.write_json(&mut json).unwrap();
+    let rows: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(rows[0].as_object().unwrap().len(), 7);
+    assert_eq!(rows[0]["georges_2"], 1400.0);
+}
+
+#[test]
+fn date_name() {
+    let history = history();
+    let table = RatingTable::compare(&[("date", &history)], PerfType::Bullet, Resolution::Daily);
+    assert_eq!(table.columns[0].name, "date_2");
+
+    let mut json = Vec::new();
+    table.write_json(&mut json).unwrap();
+    assert_eq!(String::from_utf8(json).unwrap(), r#"[{"date":"2021-01-02","date_2":1400.0}]"#);
+
+    let mut csv = Vec::new();
+    table.write_csv(&mut csv).unwrap();
+    assert_eq!(String::from_utf8(csv).unwrap(), "date,date_2\n2021-01-02,1400\n");
+}
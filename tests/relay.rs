@@ -0,0 +1,137 @@
+use rust_lichess_wrapper::core::mock::{MockResponse, MockServer};
+use rust_lichess_wrapper::core::relay::RelayDaemon;
+
+use hyper::{Method, StatusCode};
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+const PUSH: &str = "/api/broadcast/round/round1/push";
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("lichess-relay-{}-{}.pgn", name, std::process::id()))
+}
+
+fn game(board: u32, moves: &str) -> String {
+    format!(
+        "[White \"White {}\"]\r\n[Black \"Black {}\"]\r\n[Round \"1\"]\r\n[Board \"{}\"]\r\n\r\n{} *",
+        board, board, board, moves
+    )
+}
+
+fn pushed(errors: &[Option<&str>]) -> MockResponse {
+    let games: Vec<serde_json::Value> = errors
+        .iter()
+        .map(|error| match error {
+            Some(error) => serde_json::json!({ "tags": {}, "error": error }),
+            None => serde_json::json!({ "tags": {}, "moves": 1 }),
+        })
+        .collect();
+    MockResponse::json(&serde_json::json!({ "games": games }).to_string())
+}
+
+fn pushes(server: &MockServer) -> Vec<String> {
+    server
+        .requests()
+        .into_iter()
+        .filter(|request| request.method == Method::POST && request.path == PUSH)
+        .map(|request| String::from_utf8(request.body.to_vec()).unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn pushes_full_file_on_change() {
+    let server = MockServer::start().await.unwrap();
+    server.route(Method::POST, PUSH, pushed(&[None, None]));
+    let client = server.client_with_token("token");
+
+    let path = temp_path("change");
+    let mut daemon = RelayDaemon::new(&client, "round1", &path);
+    daemon.set_push_interval(Duration::ZERO);
+
+    let first = format!("{}\r\n\r\n{}\r\n", game(1, "1. e4"), game(2, "1. d4"));
+    tokio::fs::write(&path, &first).await.unwrap();
+    assert!(daemon.sync().await.unwrap().is_some());
+    assert!(daemon.sync().await.unwrap().is_none());
+
+    // Only the second game changed, but the whole file is pushed.
+    let second = format!("{}\r\n\r\n{}\r\n", game(1, "1. e4"), game(2, "1. d4 d5"));
+    tokio::fs::write(&path, &second).await.unwrap();
+    assert!(daemon.sync().await.unwrap().is_some());
+    assert!(daemon.sync().await.unwrap().is_none());
+
+    tokio::fs::remove_file(&path).await.unwrap();
+    assert_eq!(pushes(&server), vec![first, second]);
+}
+
+#[tokio::test]
+async fn pushes_again_after_game_error() {
+    let server = MockServer::start().await.unwrap();
+    server.route(Method::POST, PUSH, pushed(&[None, None]));
+    server.route_once(Method::POST, PUSH, pushed(&[None, Some("Illegal move")]));
+    let client = server.client_with_token("token");
+
+    let path = temp_path("game-error");
+    let mut daemon = RelayDaemon::new(&client, "round1", &path);
+    daemon.set_push_interval(Duration::ZERO);
+
+    let contents = format!("{}\n\n{}\n", game(1, "1. e4"), game(2, "1. d4"));
+    tokio::fs::write(&path, &contents).await.unwrap();
+    let result = daemon.sync().await.unwrap().unwrap();
+    assert_eq!(result.games[1].error.as_deref(), Some("Illegal move"));
+
+    // The file is unchanged, but the rejected game has yet to be accepted.
+    assert!(daemon.sync().await.unwrap().is_some());
+    assert!(daemon.sync().await.unwrap().is_none());
+
+    tokio::fs::remove_file(&path).await.unwrap();
+    assert_eq!(pushes(&server), vec![contents.clone(), contents]);
+}
+
+#[tokio::test]
+async fn does_not_retry_rejected_push() {
+    let server = MockServer::start().await.unwrap();
+    server.route(Method::POST, PUSH, MockResponse::error(StatusCode::NOT_FOUND, "No such round"));
+    let client = server.client_with_token("token");
+
+    let path = temp_path("rejected");
+    let mut daemon = RelayDaemon::new(&client, "round1", &path);
+    daemon.set_push_interval(Duration::ZERO);
+    daemon.set_max_retries(5);
+
+    tokio::fs::write(&path, game(1, "1. e4")).await.unwrap();
+    assert!(daemon.sync().await.is_err());
+
+    tokio::fs::remove_file(&path).await.unwrap();
+    assert_eq!(pushes(&server).len(), 1);
+}
+
+#[tokio::test]
+async fn run_waits_for_readable_file() {
+    let server = MockServer::start().await.unwrap();
+    server.route(Method::POST, PUSH, pushed(&[None]));
+    let client = server.client_with_token("token");
+
+    let path = temp_path("unreadable");
+    let mut daemon = RelayDaemon::new(&client, "round1", &path);
+    daemon.set_poll_interval(Duration::from_millis(10));
+    daemon.set_push_interval(Duration::ZERO);
+
+    // A half written file may not even be valid UTF-8.
+    tokio::fs::write(&path, b"[White \"\xc3").await.unwrap();
+
+    let pushed = async {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        tokio::fs::write(&path, game(1, "1. e4")).await.unwrap();
+        while pushes(&server).is_empty() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    };
+    tokio::select! {
+        result = daemon.run() => panic!("run returned {:?}", result.err().map(|e| e.to_string())),
+        _ = tokio::time::timeout(Duration::from_secs(10), pushed) => {}
+    }
+
+    tokio::fs::remove_file(&path).await.unwrap();
+    assert_eq!(pushes(&server), vec![game(1, "1. e4")]);
+}
@@ -0,0 +1,80 @@
+use rust_lichess_wrapper::core::mock::{MockResponse, MockServer};
+use rust_lichess_wrapper::lichess::key::Key;
+use rust_lichess_wrapper::lichess::variant::Variant;
+
+use chrono::{TimeZone, Utc};
+use hyper::Method;
+
+const SIMULS: &str = r#"{
+  "pending": [],
+  "created": [
+    {
+      "id": "aS9kDl2p",
+      "name": "Georges",
+      "fullName": "Georges simul",
+      "host": { "name": "Georges", "title": "GM", "id": "georges", "rating": 2650, "online": true },
+      "variants": [
+        { "key": "standard", "name": "Standard", "icon": "+" },
+        { "key": "kingOfTheHill", "name": "King of the Hill", "icon": "(" }
+      ],
+      "isCreated": true,
+      "isRunning": false,
+      "isFinished": false,
+      "text": "Open to all",
+      "nbApplicants": 4,
+      "nbPairings": 0,
+      "estimatedStartAt": 1659520800000
+    }
+  ],
+  "started": [
+    {
+      "id": "Lp3xTq8m",
+      "name": "Mary",
+      "fullName": "Mary simul",
+      "host": { "name": "Mary", "id": "mary", "rating": 1890, "provisional": true, "gameId": "q7ZvsdUF" },
+      "variants": [{ "key": "chess960", "name": "Chess960" }, { "key": "duckChess", "name": "Duck Chess" }],
+      "isCreated": false,
+      "isRunning": true,
+      "isFinished": false,
+      "nbApplicants": 0,
+      "nbPairings": 12,
+      "startedAt": 1659517200000
+    }
+  ],
+  "finished": []
+}"#;
+
+#[tokio::test]
+async fn simuls() {
+    let server = MockServer::start().await.unwrap();
+    server.route(Method::GET, "/api/simul", MockResponse::json(SIMULS));
+    let client = server.client();
+
+    let simuls = client.get_simuls().await.unwrap();
+    assert!(simuls.pending.is_empty());
+    assert!(simuls.finished.is_empty());
+
+    let created = &simuls.created[0];
+    assert_eq!(created.host.user.id, "georges");
+    assert_eq!(created.host.rating, Some(2650));
+    assert!(created.host.online);
+    assert_eq!(created.variants.len(), 2);
+    assert_eq!(created.variants[1].key, Variant::KingOfTheHill.into());
+    assert_eq!(created.text.as_deref(), Some("Open to all"));
+    assert_eq!(created.estimated_start_at, Utc.timestamp_millis_opt(1659520800000).single());
+    assert_eq!(created.started_at, None);
+
+    let started = &simuls.started[0];
+    assert!(started.is_running);
+    assert!(started.host.provisional);
+    assert_eq!(started.host.game_id.as_deref(), Some("q7ZvsdUF"));
+    assert_eq!(started.variants[0].icon, None);
+    // Variants unknown to the crate are kept as they were sent.
+    assert_eq!(started.variants[1].key, Key::Other("duckChess".to_string()));
+    assert_eq!(started.nb_pairings, 12);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, Method::GET);
+    assert_eq!(requests[0].query, None);
+}
@@ -0,0 +1,112 @@
+use rust_lichess_wrapper::core::api::studies::{StudyExportOptions, StudyImportOptions};
+use rust_lichess_wrapper::core::mock::{MockResponse, MockServer};
+use rust_lichess_wrapper::lichess::game::Color;
+
+use futures::StreamExt;
+use hyper::header::LAST_MODIFIED;
+use hyper::{Method, StatusCode};
+
+use std::time::Duration;
+
+const CHAPTER: &str = "[Event \"Ruy Lopez: Main line\"]\n\n1. e4 e5 2. Nf3 Nc6 3. Bb5 *\n";
+
+#[tokio::test]
+async fn export() {
+    let server = MockServer::start().await.unwrap();
+    server.route(Method::GET, "/api/study/a9zXOlBV.pgn", MockResponse::pgn(CHAPTER));
+    server.route(Method::GET, "/api/study/a9zXOlBV/Hw6Wlvyx.pgn", MockResponse::pgn(CHAPTER));
+    let client = server.client();
+
+    let options = StudyExportOptions {
+        clocks: Some(false),
+        comments: Some(true),
+        ..Default::default()
+    };
+    assert_eq!(client.export_study("a9zXOlBV", &options).await.unwrap(), CHAPTER);
+    let chapter = client
+        .export_study_chapter("a9zXOlBV", "Hw6Wlvyx", &StudyExportOptions::default())
+        .await
+        .unwrap();
+    assert_eq!(chapter, CHAPTER);
+
+    let requests = server.requests();
+    assert_eq!(requests[0].path, "/api/study/a9zXOlBV.pgn");
+    assert_eq!(requests[0].query.as_deref(), Some("clocks=false&comments=true"));
+    assert_eq!(requests[1].path, "/api/study/a9zXOlBV/Hw6Wlvyx.pgn");
+}
+
+#[tokio::test]
+async fn last_modified() {
+    let server = MockServer::start().await.unwrap();
+    server.route(
+        Method::HEAD,
+        "/api/study/a9zXOlBV.pgn",
+        MockResponse::status(StatusCode::OK).with_header(LAST_MODIFIED, "Tue, 02 Aug 2022 14:03:41 GMT"),
+    );
+    server.route(Method::HEAD, "/api/study/unchanged.pgn", MockResponse::status(StatusCode::OK));
+    let client = server.client();
+
+    let modified = client.get_study_last_modified("a9zXOlBV").await.unwrap().unwrap();
+    assert_eq!(modified.to_rfc3339(), "2022-08-02T14:03:41+00:00");
+    assert_eq!(client.get_study_last_modified("unchanged").await.unwrap(), None);
+    assert!(server.requests().iter().all(|request| request.method == Method::HEAD));
+}
+
+#[tokio::test]
+async fn list() {
+    let server = MockServer::start().await.unwrap();
+    server.route(
+        Method::GET,
+        "/api/study/by/georges",
+        MockResponse::ndjson(
+            concat!(
+                r#"{"id":"a9zXOlBV","name":"Openings","createdAt":1659448421000,"updatedAt":1659449000000}"#,
+                "\n",
+                r#"{"id":"Rl2ZkP0c","name":"Endgames","createdAt":1659448422000,"updatedAt":1659449001000}"#,
+            ),
+            Duration::ZERO,
+        ),
+    );
+    let client = server.client();
+
+    let studies: Vec<_> = client
+        .list_user_studies("georges")
+        .await
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+        .await;
+    assert_eq!(studies.len(), 2);
+    assert_eq!(studies[0].name, "Openings");
+    assert_eq!(studies[1].id, "Rl2ZkP0c");
+    assert_eq!(studies[0].created_at.timestamp_millis(), 1659448421000);
+}
+
+#[tokio::test]
+async fn import() {
+    let server = MockServer::start().await.unwrap();
+    server.route(
+        Method::POST,
+        "/api/study/a9zXOlBV/import-pgn",
+        MockResponse::json(r#"{"chapters":[{"id":"Hw6Wlvyx","name":"Ruy Lopez"}]}"#),
+    );
+    let client = server.client_with_token("lip_secret");
+
+    let options = StudyImportOptions {
+        name: Some("Ruy Lopez".to_string()),
+        orientation: Some(Color::Black),
+        ..Default::default()
+    };
+    let chapters = client.import_study_pgn("a9zXOlBV", CHAPTER, &options).await.unwrap();
+    assert_eq!(chapters.len(), 1);
+    assert_eq!(chapters[0].id, "Hw6Wlvyx");
+
+    let body = String::from_utf8(server.requests()[0].body.to_vec()).unwrap();
+    let fields: Vec<&str> = body.split('&').collect();
+    assert!(fields[0].starts_with("pgn=%5BEvent%20%22Ruy%20Lopez"));
+    assert_eq!(fields[1..], ["name=Ruy%20Lopez", "orientation=black"]);
+
+    // Importing requires authentication, so nothing is sent without it.
+    assert!(server.client().import_study_pgn("a9zXOlBV", CHAPTER, &options).await.is_err());
+    assert_eq!(server.requests().len(), 1);
+}
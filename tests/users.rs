@@ -0,0 +1,119 @@
+use rust_lichess_wrapper::core::client::{Client, ClientError};
+use rust_lichess_wrapper::lichess::performances::Performances;
+use rust_lichess_wrapper::lichess::title::Title;
+use rust_lichess_wrapper::lichess::user::{ExtendedUser, LightUser, User};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+const USER: &str = include_str!("fixtures/user.json");
+const EXTENDED_USER: &str = include_str!("fixtures/extended_user.json");
+
+/// Deserialize a fixture, and check that serializing the result and
+/// deserializing it again yields the same value.
+fn round_trip<T: Serialize + DeserializeOwned>(fixture: &str) -> T {
+    let parsed: T = serde_json::from_str(fixture).unwrap();
+    let serialized = serde_json::to_value(&parsed).unwrap();
+    let reparsed: T = serde_json::from_value(serialized.clone()).unwrap();
+    assert_eq!(serialized, serde_json::to_value(&reparsed).unwrap());
+    reparsed
+}
+
+#[test]
+fn user_round_trip() {
+    let user: User = round_trip(USER);
+    assert_eq!(user.id, "georges");
+    assert_eq!(user.title, Some(Title::NM));
+    assert_eq!(user.perfs.blitz.rating, 1609);
+    assert_eq!(user.profile.real_name.as_deref(), Some("Thibault Duplessis"));
+    assert_eq!(user.profile.dsb_rating, Some(1500));
+    assert_eq!(user.profile.links, ["github.com/ornicar", "mas.to/@thibault"]);
+}
+
+#[test]
+fn extended_user_round_trip() {
+    let extended: ExtendedUser = round_trip(EXTENDED_USER);
+    assert_eq!(extended.user.username, "Georges");
+    assert_eq!(extended.count.import, 66);
+    assert_eq!(extended.following, Some(false));
+
+    // The fields shared with User serialize exactly as User does.
+    let fixture: Value = serde_json::from_str(EXTENDED_USER).unwrap();
+    let serialized = serde_json::to_value(&extended).unwrap();
+    let user = serde_json::to_value(&extended.user).unwrap();
+    for (key, value) in user.as_object().unwrap() {
+        assert_eq!(serialized.get(key), Some(value), "{}", key);
+        if let Some(recorded) = fixture.get(key).filter(|_| key != "perfs") {
+            assert_eq!(recorded, value, "{}", key);
+        }
+    }
+}
+
+#[test]
+fn unknown_title() {
+    let fixture = USER.replace("\"NM\"", "\"XM\"");
+    let user: User = round_trip(&fixture);
+    assert_eq!(user.title, Some(Title::Other("XM".to_string())));
+}
+
+#[test]
+fn unknown_perf() {
+    let fixture = r#"{
+        "blitz": { "games": 12, "rating": 1609, "rd": 60, "prog": 8 },
+        "storm": { "runs": 3, "score": 21 },
+        "duckChess": { "games": 2, "rating": 1433, "rd": 240, "prog": 0, "prov": true }
+    }"#;
+    let perfs: Performances = round_trip(fixture);
+    assert_eq!(perfs.blitz.rating, 1609);
+    assert_eq!(perfs.storm.score, 21);
+    // Known perfs absent from the response keep their defaults.
+    assert_eq!(perfs.atomic.rating, 1500);
+
+    // The unknown perf is kept whole, and only there.
+    assert_eq!(perfs.other.len(), 1);
+    assert_eq!(perfs.other["duckChess"]["rating"], 1433);
+    assert!(perfs.iter().all(|(_, perf)| perf.rating != 1433));
+
+    let serialized = serde_json::to_value(&perfs).unwrap();
+    let fixture: Value = serde_json::from_str(fixture).unwrap();
+    assert_eq!(serialized["duckChess"], fixture["duckChess"]);
+}
+
+#[test]
+fn titled() {
+    let titled: Vec<Title> = Title::ALL.into_iter().filter(Title::is_titled).collect();
+    assert_eq!(titled.len(), 10);
+    assert!(Title::GM.is_titled());
+    assert!(Title::WNM.is_titled());
+    assert!(!Title::LM.is_titled());
+    assert!(!Title::BOT.is_titled());
+    assert!(Title::BOT.is_bot());
+    assert!(!Title::Other("XM".to_string()).is_titled());
+}
+
+#[test]
+fn conversions() {
+    let extended: ExtendedUser = serde_json::from_str(EXTENDED_USER).unwrap();
+    let user = User::from(extended);
+    assert_eq!(user.username, "Georges");
+
+    let light = LightUser::from(user);
+    assert_eq!(light.id, "georges");
+    assert_eq!(light.name, "Georges");
+    assert_eq!(light.title, Some(Title::NM));
+    assert!(light.patron);
+}
+
+#[tokio::test]
+async fn status_id_limit() {
+    let ids: Vec<String> = (0..101).map(|i| format!("user{}", i)).collect();
+    let ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+
+    // The request is refused before anything is sent, so no server is needed.
+    let err = Client::new().get_user_statuses(&ids, false, false).await.unwrap_err();
+    assert!(matches!(
+        err.downcast_ref(),
+        Some(ClientError::TooManyIds { given: 101, max: 100 })
+    ));
+}